@@ -3,13 +3,14 @@
 layout(location = 0) in VertexData {
     vec4 color;
     vec2 rel;
+    float inner;
 } vertex;
 
 layout(location = 0) out vec4 out_color;
 
 void main() {
     float sqr = dot(vertex.rel, vertex.rel);
-    if (sqr <= 1) {
+    if (sqr <= 1 && sqr >= vertex.inner * vertex.inner) {
         out_color = vertex.color;
     } else {
         discard;
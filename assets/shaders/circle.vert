@@ -3,16 +3,19 @@
 layout(location = 0) in vec2 pos;
 layout(location = 1) in vec4 color;
 layout(location = 2) in vec2 rel;
+layout(location = 3) in float inner;
 
 layout(location = 0) out VertexData {
     vec4 color;
     vec2 rel;
+    float inner;
 } vertex;
 
 
 void main() {
     vertex.color = color;
     vertex.rel = rel;
+    vertex.inner = inner;
 
     gl_Position = vec4(pos, 0., 1.);
 }
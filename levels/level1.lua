@@ -3,7 +3,8 @@ local LE = LevelEvent
 local SB = BallEnemy.Simple
 local DB = BallEnemy.Double
 
-local s = 7
+-- Speeds are in pixels per second
+local s = 420
 local r = 25
 
 local top_bottom = {HorizontalLineSide.Top, HorizontalLineSide.Bottom}
@@ -70,6 +71,7 @@ LE.CustomSpawn {
 LE.WaitUntilNoEnemies()
 
 -- Multiple Vs from the left
+LE.Checkpoint("Vs")
 for i = 1, 5 do
     LE.CustomSpawn {
         indicator_duration = 0.5,
@@ -90,6 +92,7 @@ LE.WaitUntilNoEnemies()
 
 
 -- Lines from all sides that close around you
+LE.Checkpoint("Closing lines")
 LE.Wait(1)
 for _, side in ipairs(top_bottom) do
     for _, placement in ipairs { HorizontalLinePlacement.FromLeft, HorizontalLinePlacement.FromRight } do
@@ -135,6 +138,7 @@ end
 LE.WaitUntilNoEnemies()
 
 -- First double ball
+LE.Checkpoint("Doubles")
 LE.CustomSpawn {
     indicator_duration = 2,
     formation = F.Single {
@@ -187,6 +191,7 @@ LE.WaitUntilNoEnemies()
 
 
 -- Second wave of doubles
+LE.Checkpoint("Second wave")
 -- Two multiples coming from the top followed by horizontal lines from top and bottom
 LE.Wait(1.5)
 for i = 1, 2 do
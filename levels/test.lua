@@ -14,8 +14,8 @@ for i = 1, 3 do
         enemy = BallEnemy.Simple,
         -- Initial position of the ball
         pos = {-20, HEIGHT/2},
-        -- Ball speed
-        speed = {10, 0},
+        -- Ball speed, in pixels per second
+        speed = {600, 0},
         -- Ball radius (default is 20)
         radius = 10 * i,
     })
@@ -35,12 +35,50 @@ LE.CustomSpawn {
     formation = F.Single {
         enemy = BallEnemy.Simple,
         pos = {WIDTH / 2, -20},
-        speed = {0, 10},
+        speed = {0, 600},
     }
 }
 
 LE.WaitUntilNoEnemies()
 
+LE.CustomSpawn {
+    -- FollowPlayer.Intercept aims where the player will be if they keep moving
+    -- the same way. FollowPlayer.Aim is the same as true.
+    follow_player = FollowPlayer.Intercept,
+    formation = F.Single {
+        enemy = BallEnemy.Simple,
+        pos = {WIDTH / 2, HEIGHT + 20},
+        speed = {0, 600},
+    }
+}
+
+LE.WaitUntilNoEnemies()
+
+-- A single enemy shielded by smaller enemies orbiting around it
+LE.Spawn(F.Single {
+    enemy = BallEnemy.Double,
+    pos = {-60, HEIGHT / 2},
+    speed = {300, 0},
+    radius = 30,
+    satellites = Satellites {
+        -- Type of the orbiting enemies
+        enemy = BallEnemy.Simple,
+        -- How many satellites, evenly spaced around the parent
+        amount = 6,
+        -- Radius of each satellite (default 10)
+        radius = 10,
+        -- Distance from the parent's center (default 60)
+        orbit_radius = 60,
+        -- Radians per second, negative is clockwise (default 3)
+        angular_speed = 3,
+        -- Release (default) lets satellites fly off when the parent dies,
+        -- Destroy kills them with it
+        on_parent_death = SatelliteFate.Destroy,
+    },
+})
+
+LE.WaitUntilNoEnemies()
+
 LE.SetDefaultIndicatorDuration(1.)
 
 for i = 1, 3 do
@@ -54,7 +92,7 @@ for i = 1, 3 do
         -- Initial position of the ball
         pos = {WIDTH + 20, HEIGHT / 4 * i},
         -- Balls speed (and direction)
-        speed = {-10, 0},
+        speed = {-600, 0},
         -- Radius of all balls (default is 20)
         radius = nil,
     })
@@ -66,8 +104,8 @@ for i = 1, 3 do
         enemies = {BallEnemy.Simple, BallEnemy.Double},
         -- Whether the V spawns left or right
         side = VerticalLineSide.Left,
-        -- Horizontal speed of each ball (Default 15). Always positive!
-        speed = 10,
+        -- Horizontal speed of each ball (Default 900). Always positive!
+        speed = 600,
         -- How many enemies are Spawned (Must be at most 255, and ODD!)
         amount = 15,
         -- Radius of each ball (default 20)
@@ -95,8 +133,8 @@ LE.Spawn(F.VerticalLine {
     enemies = {BallEnemy.Simple},
     -- Whether the line Spawns left or right
     side = VerticalLineSide.Left,
-    -- Horizontal speed of each ball (Default 15). Always positive!
-    speed = 10,
+    -- Horizontal speed of each ball (Default 900). Always positive!
+    speed = 600,
     -- How many enemies are Spawned (Must be at most 255)
     amount = 15,
     -- Radius of each ball (default 20)
@@ -161,8 +199,8 @@ LE.Spawn(F.Circle {
     amount = 12,
     -- Enemy radius (default is 20)
     enemy_radius = 19,
-    -- Speed of each ball (Default 15). Always positive!
-    speed = 10,
+    -- Speed of each ball (Default 900). Always positive!
+    speed = 600,
     -- Radius of the formation. Defaults to half the screen diagonal + enemy_radius,
     -- so the enemies will be just outside the screen.
     formation_radius = nil,
@@ -196,8 +234,8 @@ LE.Spawn(F.Spiral {
     -- "Radial" spacing between enemies. That means the distance from an enemy to
     -- the center of the formation will increase by this value for each circle
     spacing = 30,
-    -- Enemy speed (defaults to 10)
-    speed = 10,
+    -- Enemy speed (defaults to 600)
+    speed = 600,
     -- Enemy radius
     radius = 20,
 })
@@ -212,8 +250,106 @@ LE.Spawn(F.Spiral {
     amount_in_circle = 150,
     amount = 40,
     spacing = 60,
-    speed = 10,
+    speed = 600,
     radius = 20,
 })
 
-LE.WaitUntilNoEnemies()
\ No newline at end of file
+LE.WaitUntilNoEnemies()
+
+-- Behaviours are lua functions that control enemies. They are called every few seconds
+-- with the enemy position and speed (as {x, y} tables), and may return a table with
+-- new values for them and formations to spawn right away. They must not call LE functions.
+-- Speeds are those of Normal difficulty, and get scaled like everything else.
+local zigzag = Behaviour {
+    -- Seconds between calls, slowed down along with the game (default 0, every frame)
+    every = 0.5,
+    run = function(pos, speed)
+        return {
+            -- Missing values are left unchanged
+            speed = {speed[1], -speed[2]},
+        }
+    end,
+}
+
+local splitter = Behaviour {
+    every = 1,
+    run = function(pos, speed)
+        return {
+            spawn = {
+                F.Single { enemy = BallEnemy.Simple, pos = pos, speed = {speed[2], speed[1]}, radius = 10 },
+            },
+        }
+    end,
+}
+
+for i, behaviour in ipairs {zigzag, splitter} do
+    LE.CustomSpawn {
+        -- All enemies of the formation will run this behaviour
+        behaviour = behaviour,
+        formation = F.Single {
+            enemy = BallEnemy.Simple,
+            pos = {-20, HEIGHT / 3 * i},
+            speed = {300, 300},
+        },
+    }
+end
+
+LE.WaitUntilNoEnemies()
+
+-- Spawns a boss, a big enemy with a health bar that bounces on the edges of the screen
+LE.SpawnBoss {
+    pos = {WIDTH / 2, -80},
+    speed = {180, 240},
+    -- Default is 80
+    radius = 80,
+    -- How many shots it takes to kill it
+    health = 60,
+    -- Fractions of the health at which a new phase starts. The boss starts at phase 1,
+    -- and in this case goes to phase 2 at 50% health and phase 3 at 20% health.
+    phases = {0.5, 0.2},
+}
+
+-- BOSS_PHASE holds the current phase of the boss, or nil if there's no boss
+while BOSS_PHASE == 1 do
+    LE.Spawn(F.Circle { enemies = {BallEnemy.Simple}, amount = 8 })
+    LE.Wait(2)
+end
+
+-- Waits until the boss goes to the next phase or dies
+LE.WaitBossPhase()
+
+LE.WaitUntilNoEnemies()
+
+-- Obstacles can't be killed by shots, but still kill you. Use them to create lanes.
+for i = 1, 2 do
+    LE.Spawn(F.Multiple {
+        enemies = {BallEnemy.Obstacle},
+        amount = 40,
+        spacing = 0,
+        pos = {-20, HEIGHT / 3 * i},
+        speed = {240, 0},
+    })
+end
+
+LE.WaitUntilNoEnemies()
+
+-- Restricts the player to a rectangle given by its top left corner and size.
+-- The rest of the screen is shaded. Enemies are still free to move everywhere.
+LE.SetPlayArea { pos = {WIDTH / 4, HEIGHT / 4}, size = {WIDTH / 2, HEIGHT / 2} }
+LE.Wait(3)
+-- Use the whole screen to go back to normal
+LE.SetPlayArea { pos = {0, 0}, size = {WIDTH, HEIGHT} }
+
+-- Pickups give the player an extra life, a different weapon, a shield or a few
+-- seconds of slow motion. Destroyed enemies also drop them sometimes.
+LE.SpawnPickup { pickup = Pickup.Shield, pos = {WIDTH / 2, 0} }
+LE.SpawnPickup { pickup = Pickup.ExtraLife, pos = {WIDTH / 3, 0}, speed = {0, 120} }
+
+-- DIFFICULTY is one of the values of the Difficulty table. Enemy speed, radius and
+-- indicator durations are already scaled by it, so only use it for anything extra.
+if DIFFICULTY == Difficulty.Hard then
+    LE.Spawn(F.Circle { enemies = {BallEnemy.Simple}, amount = 12 })
+end
+
+-- Practice mode can start the level at any checkpoint
+LE.Checkpoint("Test end")
@@ -0,0 +1,120 @@
+//! Ordered levels of the campaign and the player's progress through them
+
+use amethyst::config::Config;
+use failure::Error;
+use log::{info, warn};
+use ron::ser::{to_string_pretty, PrettyConfig};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, fs, path::PathBuf};
+
+use crate::utils::fs::{root, user_dir};
+
+const SAVE_FILE: &str = "save.ron";
+
+/// Bumped whenever the save file changes in an incompatible way
+const SAVE_VERSION: u32 = 1;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Campaign {
+    pub levels: Vec<String>,
+}
+
+impl Campaign {
+    pub fn load() -> Self {
+        Config::load(root().join("config/campaign.ron")).unwrap_or_else(|e| {
+            warn!("Failed to read campaign: {}", e);
+            Self::default()
+        })
+    }
+
+    /// Index of a level in the campaign
+    pub fn position(&self, level_name: &str) -> Option<usize> {
+        self.levels.iter().position(|level| level == level_name)
+    }
+}
+
+/// Progress through the campaign
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SaveFile {
+    version: u32,
+    /// Names of the campaign levels finished, so progress survives changes to
+    /// the campaign
+    pub completed: BTreeSet<String>,
+}
+
+impl Default for SaveFile {
+    fn default() -> Self {
+        Self {
+            version: SAVE_VERSION,
+            completed: BTreeSet::new(),
+        }
+    }
+}
+
+impl SaveFile {
+    fn path() -> PathBuf {
+        user_dir().join(SAVE_FILE)
+    }
+
+    pub fn exists() -> bool {
+        Self::path().exists()
+    }
+
+    /// Reads the saved progress, starting over if there's none or it's from
+    /// another version
+    pub fn load() -> Self {
+        let path = Self::path();
+        if !path.exists() {
+            return Self::default();
+        }
+        match <Self as Config>::load(&path) {
+            Ok(save) if save.version == SAVE_VERSION => save,
+            Ok(save) => {
+                warn!(
+                    "Ignoring save file with version {}, expected {}",
+                    save.version, SAVE_VERSION
+                );
+                Self::default()
+            }
+            Err(e) => {
+                warn!("Failed to read save file {:?}: {}", path, e);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Error> {
+        let path = Self::path();
+        fs::create_dir_all(user_dir())?;
+        fs::write(&path, to_string_pretty(self, PrettyConfig::default())?)?;
+        info!("Saved progress to {:?}", path);
+        Ok(())
+    }
+
+    /// Whether a level of the campaign can be played, because it's the first
+    /// one or the previous one was finished. Levels outside the campaign are locked.
+    pub fn is_unlocked(&self, campaign: &Campaign, index: usize) -> bool {
+        let completed = |i: usize| {
+            campaign
+                .levels
+                .get(i)
+                .map_or(false, |level| self.completed.contains(level))
+        };
+        index < campaign.levels.len() && (index == 0 || completed(index - 1) || completed(index))
+    }
+
+    /// Index of the first level of the campaign that wasn't finished, or the last
+    /// one if they all were
+    pub fn continue_from(&self, campaign: &Campaign) -> usize {
+        campaign
+            .levels
+            .iter()
+            .position(|level| !self.completed.contains(level))
+            .unwrap_or_else(|| campaign.levels.len().saturating_sub(1))
+    }
+
+    /// Records that a level was finished, unlocking the next one
+    pub fn complete(&mut self, level_name: &str) {
+        self.completed.insert(level_name.to_owned());
+    }
+}
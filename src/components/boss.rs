@@ -0,0 +1,67 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+use log::warn;
+
+/// Component for big enemies that take many shots to kill, and change their
+/// behaviour (through the level script) as their health decreases
+#[derive(Debug, Component, Clone)]
+pub struct Boss {
+    pub health: u32,
+    pub max_health: u32,
+    /// Health fractions at which a new phase starts, in decreasing order
+    pub thresholds: Vec<f32>,
+    /// Current phase, starting from 1
+    pub phase: u8,
+}
+
+impl Boss {
+    /// Thresholds outside of (0, 1), which could never start a phase, are
+    /// ignored
+    pub fn new(health: u32, thresholds: Vec<f32>) -> Self {
+        let mut thresholds = thresholds
+            .into_iter()
+            .filter(|&threshold| {
+                let valid = threshold > 0. && threshold < 1.;
+                if !valid {
+                    warn!("Ignoring invalid boss phase threshold {}", threshold);
+                }
+                valid
+            })
+            .collect::<Vec<_>>();
+        // Can't fail, NaN was filtered out
+        thresholds.sort_by(|a, b| b.partial_cmp(a).unwrap());
+        Self {
+            health,
+            max_health: health,
+            thresholds,
+            phase: 1,
+        }
+    }
+
+    pub fn health_fraction(&self) -> f32 {
+        self.health as f32 / self.max_health.max(1) as f32
+    }
+
+    /// Takes damage, advancing phases if needed. Returns whether the boss died.
+    pub fn damage(&mut self, amount: u32) -> bool {
+        self.health = self.health.saturating_sub(amount);
+        let fraction = self.health_fraction();
+        while let Some(&threshold) = self.thresholds.get(self.phase as usize - 1) {
+            if fraction > threshold {
+                break;
+            }
+            self.phase += 1;
+        }
+        self.health == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_boss_phases() {
+        let boss = Boss::new(10, vec![std::f32::NAN, 0.5, 1.5, 0., -1.]);
+        assert_eq!(boss.thresholds, vec![0.5]);
+    }
+}
@@ -14,6 +14,11 @@ impl Circle {
     }
 }
 
+/// Draws the circle as a ring, leaving empty the inner circle
+/// with the given fraction of the radius
+#[derive(Debug, Component, Clone)]
+pub struct Ring(pub f32);
+
 pub fn collides(t1: &Transform, c1: &Circle, t2: &Transform, c2: &Circle, threshold: f32) -> bool {
     let r = (c1.radius + c2.radius - threshold).max(0.);
     (t1.0 - t2.0).norm_squared() <= r * r
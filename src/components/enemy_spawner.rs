@@ -1,30 +1,96 @@
-use amethyst::ecs::{Component, DenseVecStorage};
+use amethyst::ecs::{Component, DenseVecStorage, Entity};
 
 use amethyst::core::math::{Point2, Vector2};
 
-use crate::utils::creator::LazyCreator;
+use crate::{
+    components::{nearest_player, scripted::Behaviour, PlayerState, Scripted},
+    display::{HEIGHT, WIDTH},
+    utils::creator::LazyCreator,
+};
 
 pub trait EnemySpawnerLogic: Send + Sync + std::fmt::Debug {
-    fn do_spawn(&self, creator: &LazyCreator, pos: Point2<f32>, speed: Vector2<f32>) -> ();
+    /// Spawns the enemies, returning them
+    fn do_spawn(&self, creator: &LazyCreator, pos: Point2<f32>, speed: Vector2<f32>)
+        -> Vec<Entity>;
 }
 
 #[derive(Debug)]
 pub enum SpawnSpeed {
     Fixed(Vector2<f32>),
-    AimAtPlayer { speed: f32 },
+    /// Aim at the nearest player
+    AimAtPlayer {
+        speed: f32,
+    },
+    /// Aim at where the nearest player will be, if they keep moving with the same velocity
+    Intercept {
+        speed: f32,
+    },
+}
+
+/// Direction to shoot something from `from` with `speed` so that it hits a target at
+/// `target` moving with `target_vel`. None if it's impossible.
+pub fn intercept_direction(
+    from: Point2<f32>,
+    target: Point2<f32>,
+    target_vel: Vector2<f32>,
+    speed: f32,
+) -> Option<Vector2<f32>> {
+    // Solve |d + v * t| = speed * t for the smallest positive t
+    let d = target - from;
+    let a = target_vel.norm_squared() - speed * speed;
+    let b = 2. * d.dot(&target_vel);
+    let c = d.norm_squared();
+    let t = if a.abs() < 1e-6 {
+        -c / b
+    } else {
+        let delta = b * b - 4. * a * c;
+        if delta < 0. {
+            return None;
+        }
+        let (t1, t2) = (
+            (-b - delta.sqrt()) / (2. * a),
+            (-b + delta.sqrt()) / (2. * a),
+        );
+        match (t1.min(t2), t1.max(t2)) {
+            (t, _) if t > 0. => t,
+            (_, t) => t,
+        }
+    };
+    if t.is_finite() && t > 0. {
+        Some((d + target_vel * t).normalize())
+    } else {
+        None
+    }
 }
 
 impl EnemySpawner {
-    pub fn calc_speed(&self, player_pos: Point2<f32>) -> Vector2<f32> {
+    /// Speed of the spawned enemies, aiming at the nearest of `players` if needed.
+    /// With no players alive, enemies aim at the center of the screen.
+    pub fn calc_speed(&self, players: &[PlayerState]) -> Vector2<f32> {
+        let (player_pos, player_vel) = nearest_player(players, self.position)
+            .map(|player| (player.pos, player.vel))
+            .unwrap_or((Point2::new(WIDTH / 2., HEIGHT / 2.), Vector2::new(0., 0.)));
+        let aim = |speed| (player_pos - self.position).normalize() * speed;
         match self.spawn_speed {
             SpawnSpeed::Fixed(s) => s,
-            SpawnSpeed::AimAtPlayer { speed } => (player_pos - self.position).normalize() * speed,
+            SpawnSpeed::AimAtPlayer { speed } => aim(speed),
+            SpawnSpeed::Intercept { speed } => {
+                intercept_direction(self.position, player_pos, player_vel, speed)
+                    .map(|dir| dir * speed)
+                    .unwrap_or_else(|| aim(speed))
+            }
         }
     }
 
-    pub fn do_spawn(&self, creator: &LazyCreator, player_pos: Point2<f32>) {
-        self.logic
-            .do_spawn(creator, self.position, self.calc_speed(player_pos));
+    pub fn do_spawn(&self, creator: &LazyCreator, players: &[PlayerState]) {
+        let enemies = self
+            .logic
+            .do_spawn(creator, self.position, self.calc_speed(players));
+        if let Some(behaviour) = self.behaviour {
+            for enemy in enemies {
+                creator.lazy.insert(enemy, Scripted::new(behaviour));
+            }
+        }
     }
 }
 
@@ -36,4 +102,62 @@ pub struct EnemySpawner {
     pub spawn_speed: SpawnSpeed,
     pub logic: Box<dyn EnemySpawnerLogic>,
     pub spawn_at: f64,
+    pub behaviour: Option<Behaviour>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct NoEnemies;
+
+    impl EnemySpawnerLogic for NoEnemies {
+        fn do_spawn(&self, _: &LazyCreator, _: Point2<f32>, _: Vector2<f32>) -> Vec<Entity> {
+            vec![]
+        }
+    }
+
+    #[test]
+    fn test_intercept() {
+        let from = Point2::new(0., 0.);
+        // Standing still is the same as aiming
+        let dir = intercept_direction(from, Point2::new(10., 0.), Vector2::new(0., 0.), 1.);
+        assert!((dir.unwrap() - Vector2::new(1., 0.)).norm() < 1e-5);
+        // Target moving up, so we need to shoot at 45 degrees
+        let dir = intercept_direction(
+            from,
+            Point2::new(10., 0.),
+            Vector2::new(0., 1.),
+            2f32.sqrt(),
+        );
+        assert!((dir.unwrap() - Vector2::new(1., 1.).normalize()).norm() < 1e-5);
+        // Target running away faster than the shot
+        let dir = intercept_direction(from, Point2::new(10., 0.), Vector2::new(5., 0.), 1.);
+        assert!(dir.is_none());
+    }
+
+    #[test]
+    fn test_aim_at_nearest_player() {
+        let spawner = EnemySpawner {
+            position: Point2::new(0., 0.),
+            spawn_speed: SpawnSpeed::AimAtPlayer { speed: 2. },
+            logic: Box::new(NoEnemies),
+            spawn_at: 0.,
+            behaviour: None,
+        };
+        let still = Vector2::new(0., 0.);
+        let players = [
+            PlayerState {
+                pos: Point2::new(0., 100.),
+                vel: still,
+            },
+            PlayerState {
+                pos: Point2::new(10., 0.),
+                vel: still,
+            },
+        ];
+        assert_eq!(spawner.calc_speed(&players), Vector2::new(2., 0.));
+        assert_eq!(spawner.calc_speed(&players[..1]), Vector2::new(0., 2.));
+    }
 }
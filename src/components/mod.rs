@@ -1,17 +1,27 @@
 mod ball_enemy;
+mod boss;
 pub mod circle;
 mod color;
 pub mod enemy_spawner;
 mod particle;
+pub mod pickup;
 mod player;
+mod rectangle;
+mod scripted;
 pub mod transform;
 mod triangle;
+pub mod weapon;
 
 pub use ball_enemy::BallEnemy;
-pub use circle::Circle;
+pub use boss::Boss;
+pub use circle::{Circle, Ring};
 pub use color::Color;
 pub use enemy_spawner::EnemySpawner;
 pub use particle::Particle;
+pub use pickup::Pickup;
 pub use player::*;
-pub use transform::{Moving, Transform};
+pub use rectangle::Rectangle;
+pub use scripted::Scripted;
+pub use transform::{Moving, Orbit, Transform};
 pub use triangle::Triangle;
+pub use weapon::{Arsenal, Gun, Weapon};
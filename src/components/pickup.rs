@@ -0,0 +1,30 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+use crate::components::Color;
+
+pub use crate::editor::reader::Pickup;
+
+/// Default speed of pickups, in pixels per second. They drift down slowly.
+pub const PICKUP_SPEED: f32 = 60.;
+
+impl Component for Pickup {
+    type Storage = DenseVecStorage<Pickup>;
+}
+
+impl Pickup {
+    pub const ALL: [Pickup; 4] = [
+        Pickup::ExtraLife,
+        Pickup::Weapon,
+        Pickup::Shield,
+        Pickup::SlowMotion,
+    ];
+
+    pub fn color(&self) -> Color {
+        match self {
+            Pickup::ExtraLife => Color::rgb(0.2, 0.9, 0.3),
+            Pickup::Weapon => Color::rgb(1., 0.6, 0.1),
+            Pickup::Shield => Color::rgb(0.2, 0.9, 0.9),
+            Pickup::SlowMotion => Color::rgb(0.9, 0.4, 0.9),
+        }
+    }
+}
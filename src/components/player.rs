@@ -1,4 +1,10 @@
-use amethyst::ecs::{Component, NullStorage};
+use amethyst::{
+    core::math::{Point2, Vector2},
+    ecs::{storage::MaskedStorage, Component, DenseVecStorage, Entity, Join, NullStorage, Storage},
+};
+use std::ops::Deref;
+
+use crate::components::{Color, Transform};
 
 /// Creates a component that's just a tag, that is, it has no data inside.
 macro_rules! tag_components {
@@ -11,4 +17,126 @@ macro_rules! tag_components {
     };
 }
 
-tag_components!(Player, Shot, InScreen);
+tag_components!(InScreen, Shield);
+
+/// Most players that can play at the same time
+pub const MAX_PLAYERS: usize = 2;
+
+/// A player, controlled with the bindings for its `id`
+#[derive(Debug, Component, Clone)]
+pub struct Player {
+    pub id: usize,
+    /// Current speed, in pixels per second
+    pub velocity: Vector2<f32>,
+}
+
+impl Player {
+    pub fn new(id: usize) -> Self {
+        Self {
+            id,
+            velocity: Vector2::new(0., 0.),
+        }
+    }
+
+    pub fn color(id: usize) -> Color {
+        match id {
+            0 => Color::rgb(0.3, 0.4, 1.),
+            _ => Color::rgb(1., 0.8, 0.2),
+        }
+    }
+}
+
+/// Where a living player is and how it's moving, for enemies aiming at it
+#[derive(Debug, Clone, Copy)]
+pub struct PlayerState {
+    pub pos: Point2<f32>,
+    pub vel: Vector2<f32>,
+}
+
+/// State of every living player
+pub fn living_players<P, T>(
+    players: &Storage<Player, P>,
+    transforms: &Storage<Transform, T>,
+) -> Vec<PlayerState>
+where
+    P: Deref<Target = MaskedStorage<Player>>,
+    T: Deref<Target = MaskedStorage<Transform>>,
+{
+    (players, transforms)
+        .join()
+        .map(|(player, transform)| PlayerState {
+            pos: transform.0,
+            vel: player.velocity,
+        })
+        .collect()
+}
+
+/// The player closest to `pos`, if any is alive
+pub fn nearest_player(players: &[PlayerState], pos: Point2<f32>) -> Option<PlayerState> {
+    players.iter().copied().min_by(|a, b| {
+        let (da, db) = ((a.pos - pos).norm(), (b.pos - pos).norm());
+        da.partial_cmp(&db).unwrap()
+    })
+}
+
+/// Left where a player died while their partners were alive. Partners can
+/// revive them by staying close to it.
+#[derive(Debug, Component, Clone)]
+pub struct Downed {
+    pub id: usize,
+    /// Seconds partners have spent reviving
+    pub progress: f64,
+}
+
+/// Lets the player dash, moving quickly without being hit for a short while
+#[derive(Debug, Component, Clone)]
+pub struct Dash {
+    /// When the player can dash again
+    pub ready_at: f64,
+    /// When the current (or last) dash ends
+    pub until: f64,
+    /// Speed of the current dash, in pixels per second
+    pub velocity: Vector2<f32>,
+}
+
+impl Default for Dash {
+    fn default() -> Self {
+        Self {
+            ready_at: 0.,
+            until: 0.,
+            velocity: Vector2::new(0., 0.),
+        }
+    }
+}
+
+impl Dash {
+    pub fn is_active(&self, now: f64) -> bool {
+        now < self.until
+    }
+}
+
+/// A projectile shot by the player
+#[derive(Debug, Component, Clone)]
+pub struct Shot {
+    pub damage: u32,
+    /// Enemies it can still go through
+    pub piercing: u32,
+    /// Enemies already hit, so piercing shots don't hit them again
+    pub hit: Vec<Entity>,
+}
+
+/// The player can't be hit until the given time
+#[derive(Debug, Component, Clone)]
+pub struct Invulnerable {
+    pub until: f64,
+}
+
+/// How an enemy passing close to the players is going. Enemies without it
+/// haven't been close yet.
+#[derive(Debug, Component, Clone, Copy, PartialEq)]
+pub enum Graze {
+    /// Inside the band around a player's hitbox
+    InBand,
+    /// Left the band or hit a player, so it can't graze again
+    Done,
+}
@@ -0,0 +1,19 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+use amethyst::core::math::Point2;
+
+/// Component for the axis aligned rectangles we wish to draw to the screen
+#[derive(Debug, Component, Clone)]
+pub struct Rectangle {
+    pub min: Point2<f32>,
+    pub max: Point2<f32>,
+}
+
+impl Rectangle {
+    pub fn new<P: Into<Point2<f32>>>(min: P, max: P) -> Self {
+        Self {
+            min: min.into(),
+            max: max.into(),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+pub use crate::editor::reader::Behaviour;
+
+/// Component for enemies controlled by a behaviour from the level script
+#[derive(Debug, Component, Clone)]
+pub struct Scripted {
+    pub behaviour: Behaviour,
+    /// Game time of the next run, set the first time it's checked
+    pub next_run: Option<f64>,
+}
+
+impl Scripted {
+    pub fn new(behaviour: Behaviour) -> Self {
+        Self {
+            behaviour,
+            next_run: None,
+        }
+    }
+
+    /// Returns whether the behaviour should run at the given game time
+    pub fn tick(&mut self, now: f64) -> bool {
+        let every = self.behaviour.every as f64;
+        let next_run = *self.next_run.get_or_insert(now + every);
+        if now < next_run {
+            return false;
+        }
+        // Scheduled from the previous run rather than now, so it doesn't
+        // drift with the frame rate
+        self.next_run = Some(next_run + every);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scripted_runs_on_game_time() {
+        let mut scripted = Scripted::new(Behaviour { id: 1, every: 0.5 });
+        assert!(!scripted.tick(10.));
+        assert!(!scripted.tick(10.25));
+        assert!(scripted.tick(10.5));
+        // Late frames don't push the following runs back
+        assert!(scripted.tick(11.1));
+        assert!(!scripted.tick(11.2));
+        assert!(scripted.tick(11.5));
+        let mut every_frame = Scripted::new(Behaviour { id: 1, every: 0. });
+        assert!(every_frame.tick(10.));
+        assert!(every_frame.tick(10.01));
+    }
+}
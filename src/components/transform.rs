@@ -1,5 +1,7 @@
-use amethyst::core::math::{Point2, Vector2};
-use amethyst::ecs::{Component, DenseVecStorage};
+use amethyst::core::math::{Point2, Rotation2, Vector2};
+use amethyst::ecs::{Component, DenseVecStorage, Entity};
+
+pub use crate::editor::reader::SatelliteFate;
 
 pub type Position = Point2<f32>;
 
@@ -18,6 +20,7 @@ impl From<Position> for Transform {
     }
 }
 
+/// Speed in pixels per second
 #[derive(Debug, Component, Clone)]
 pub struct Moving(pub Vector2<f32>);
 
@@ -32,3 +35,28 @@ impl From<Vector2<f32>> for Moving {
         Self(d)
     }
 }
+
+/// Positions an entity relative to its parent's `Transform`, rotating around it
+/// at a fixed distance. Used by enemy satellites.
+#[derive(Debug, Component, Clone)]
+pub struct Orbit {
+    pub parent: Entity,
+    pub radius: f32,
+    /// Radians per second
+    pub angular_speed: f32,
+    pub angle: f32,
+    pub fate: SatelliteFate,
+}
+
+impl Orbit {
+    /// Position relative to the parent's center
+    pub fn offset(&self) -> Vector2<f32> {
+        Rotation2::new(self.angle) * Vector2::new(self.radius, 0.)
+    }
+
+    /// Speed relative to the parent's speed
+    pub fn tangential_speed(&self) -> Vector2<f32> {
+        let offset = self.offset();
+        Vector2::new(-offset.y, offset.x) * self.angular_speed
+    }
+}
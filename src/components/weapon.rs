@@ -0,0 +1,99 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+use serde::{Deserialize, Serialize};
+
+/// How a weapon shoots, read from config/weapons.ron
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Weapon {
+    pub name: String,
+    /// Seconds between shots (or bursts)
+    pub interval: f64,
+    /// Projectiles in each shot
+    pub count: u32,
+    /// Angle in degrees between the first and last projectiles of a shot
+    pub spread: f32,
+    /// Speed of the projectiles in pixels per second
+    pub speed: f32,
+    pub radius: f32,
+    pub damage: u32,
+    /// How many enemies a projectile goes through before disappearing
+    pub piercing: u32,
+    pub burst: Option<Burst>,
+    pub charge: Option<Charge>,
+}
+
+impl Default for Weapon {
+    fn default() -> Self {
+        Self {
+            name: "Basic".to_string(),
+            interval: 0.18,
+            count: 1,
+            spread: 0.,
+            speed: 1200.,
+            radius: 5.,
+            damage: 1,
+            piercing: 0,
+            burst: None,
+            charge: None,
+        }
+    }
+}
+
+/// Shoots multiple times each time the weapon fires
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Burst {
+    /// Shots in a burst, including the first one
+    pub shots: u32,
+    /// Seconds between shots of a burst
+    pub delay: f64,
+}
+
+/// Holding the shoot button charges the weapon, which fires on release
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Charge {
+    /// Seconds until the weapon is fully charged
+    pub time: f64,
+    /// Radius and damage multiplier when fully charged
+    pub max_scale: f32,
+}
+
+/// Every weapon the players can switch between
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Arsenal {
+    pub weapons: Vec<Weapon>,
+}
+
+impl Default for Arsenal {
+    fn default() -> Self {
+        Self {
+            weapons: vec![Weapon::default()],
+        }
+    }
+}
+
+/// Shooting state of a player
+#[derive(Debug, Component, Clone, Default)]
+pub struct Gun {
+    /// Index of the current weapon in the `Arsenal`
+    pub weapon: usize,
+    pub last_shot: Option<f64>,
+    /// Shots left in the current burst
+    pub burst_left: u32,
+    /// When the shoot button started being held for a charged weapon
+    pub charging_since: Option<f64>,
+}
+
+impl Gun {
+    /// Switches to the weapon `offset` positions away, wrapping around
+    pub fn switch(&mut self, offset: isize, arsenal: &Arsenal) {
+        let n = arsenal.weapons.len() as isize;
+        self.weapon = (self.weapon as isize + offset).rem_euclid(n) as usize;
+        self.burst_left = 0;
+        self.charging_since = None;
+    }
+
+    pub fn ready(&self, now: f64, weapon: &Weapon) -> bool {
+        self.last_shot
+            .map_or(true, |last| now - last >= weapon.interval)
+    }
+}
@@ -0,0 +1,87 @@
+//! Configuration read from the `config` directory
+
+use serde::{Deserialize, Serialize};
+
+/// Gameplay tweaks, read from config/gameplay.ron
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameplayConfig {
+    /// Lives at the start of a level
+    pub lives: u8,
+    /// Seconds between dying and respawning
+    pub respawn_delay: f64,
+    /// Seconds during which the player can't be hit after respawning
+    pub invulnerability: f64,
+    /// Enemies closer than this to the respawn point are destroyed on respawn
+    pub respawn_clear_radius: f32,
+    /// Seconds a partner must stay near a downed player to revive them
+    pub revive_time: f64,
+    /// How close to a downed player a partner must be, from edge to edge
+    pub revive_radius: f32,
+    /// Speed of the player while dashing, in pixels per second
+    pub dash_speed: f32,
+    /// Seconds each dash lasts, during which the player can't be hit
+    pub dash_duration: f64,
+    /// Seconds between the start of a dash and the next one
+    pub dash_cooldown: f64,
+    /// Seconds after a kill during which the next one continues the combo
+    pub combo_time: f64,
+    /// Kills needed to raise the combo multiplier by one
+    pub combo_step: u32,
+    pub max_multiplier: u32,
+    /// Bombs at the start of each life
+    pub bombs: u8,
+    /// Damage bombs do to bosses
+    pub bomb_boss_damage: u32,
+    /// Chance that a destroyed enemy drops a pickup, from 0 to 1
+    pub pickup_chance: f32,
+    /// Seconds during which the player can't be hit after their shield breaks
+    pub shield_invulnerability: f64,
+    /// Real seconds the slow motion pickup lasts
+    pub slow_motion_duration: f64,
+    /// Speed of the game during slow motion
+    pub slow_motion_scale: f32,
+    /// Enemies passing this close to a player's hitbox graze it
+    pub graze_distance: f32,
+    /// Points for each enemy grazed
+    pub graze_points: u64,
+    /// Fraction of the psycho meter filled by each enemy grazed
+    pub graze_meter: f32,
+    /// Fraction of the psycho meter filled by each kill
+    pub kill_meter: f32,
+    /// Fraction of the psycho meter spent each real second it's used
+    pub psycho_drain: f32,
+    /// Speed of the game while the psycho meter is used
+    pub psycho_scale: f32,
+}
+
+impl Default for GameplayConfig {
+    fn default() -> Self {
+        Self {
+            lives: 3,
+            respawn_delay: 1.,
+            invulnerability: 2.,
+            respawn_clear_radius: 200.,
+            revive_time: 2.,
+            revive_radius: 30.,
+            dash_speed: 1800.,
+            dash_duration: 0.15,
+            dash_cooldown: 1.,
+            combo_time: 2.,
+            combo_step: 5,
+            max_multiplier: 8,
+            bombs: 2,
+            bomb_boss_damage: 10,
+            pickup_chance: 0.05,
+            shield_invulnerability: 1.,
+            slow_motion_duration: 4.,
+            slow_motion_scale: 0.5,
+            graze_distance: 20.,
+            graze_points: 5,
+            graze_meter: 0.05,
+            kill_meter: 0.02,
+            psycho_drain: 0.25,
+            psycho_scale: 0.3,
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! Difficulty presets, applied on top of the levels and the gameplay config
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Difficulty::Normal
+    }
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    /// Name shown on screen and given to level scripts
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    /// The next difficulty in `ALL`, going back to the first one after the last
+    pub fn cycle(self, offset: isize) -> Self {
+        let n = Self::ALL.len() as isize;
+        let i = Self::ALL.iter().position(|&d| d == self).unwrap() as isize;
+        Self::ALL[(i + offset).rem_euclid(n) as usize]
+    }
+
+    /// Multiplies the speed of enemies
+    pub fn enemy_speed(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.8,
+            Difficulty::Normal => 1.,
+            Difficulty::Hard => 1.2,
+        }
+    }
+
+    /// Multiplies how long spawn indicators are shown
+    pub fn indicator_duration(self) -> f64 {
+        match self {
+            Difficulty::Easy => 1.5,
+            Difficulty::Normal => 1.,
+            Difficulty::Hard => 0.75,
+        }
+    }
+
+    /// Multiplies the radius of enemies
+    pub fn enemy_radius(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.85,
+            Difficulty::Normal => 1.,
+            Difficulty::Hard => 1.15,
+        }
+    }
+
+    /// How much an enemy can overlap the player without hitting them, in pixels
+    pub fn hitbox_tolerance(self) -> f32 {
+        match self {
+            Difficulty::Easy => 5.,
+            Difficulty::Normal => 2.,
+            Difficulty::Hard => 0.,
+        }
+    }
+
+    /// Lives at the start of a level, given the ones in the gameplay config
+    pub fn lives(self, base: u8) -> u8 {
+        match self {
+            Difficulty::Easy => base.saturating_add(2),
+            Difficulty::Normal => base,
+            Difficulty::Hard => base.saturating_sub(1).max(1),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 #[macro_use]
 pub mod shape_drawer;
 pub mod screen;
+pub mod text;
 
 pub use screen::{HEIGHT, WIDTH};
@@ -41,7 +41,7 @@ use glsl_layout::*;
 use lazy_static::lazy_static;
 
 use crate::{
-    components::{Circle, Color, Transform, Triangle},
+    components::{Circle, Color, Rectangle, Ring, Transform, Triangle},
     display::{HEIGHT, WIDTH},
 };
 
@@ -214,7 +214,7 @@ impl<B: Backend> RenderPlugin<B> for RenderCircles {
         world: &mut World,
         _builder: &mut DispatcherBuilder<'a, 'b>,
     ) -> amethyst::Result<()> {
-        register!(Color, Circle, Transform, Triangle -> world);
+        register!(Color, Circle, Transform, Triangle, Rectangle, Ring -> world);
         Ok(())
     }
 
@@ -273,22 +273,30 @@ impl<B: Backend> RenderGroup<B, World> for DrawShape<B> {
         _subpass: hal::pass::Subpass<'_, B>,
         world: &World,
     ) -> PrepareResult {
-        let (circles, triangles, transforms, colors) = <(
+        let (circles, rings, triangles, rectangles, transforms, colors) = <(
             ReadStorage<'_, Circle>,
+            ReadStorage<'_, Ring>,
             ReadStorage<'_, Triangle>,
+            ReadStorage<'_, Rectangle>,
             ReadStorage<'_, Transform>,
             ReadStorage<'_, Color>,
         )>::fetch(world);
 
         // Create all vertices
-        let circle_data = (&circles, &transforms, &colors)
+        let circle_data = (&circles, &transforms, &colors, rings.maybe())
             .join()
-            .flat_map(|(circle, t, color)| circle.get_vertices(t, color))
+            .flat_map(|(circle, t, color, ring)| circle.get_vertices(t, color, ring))
             .collect::<Box<[CircleArgs]>>();
 
+        // Rectangles are just two triangles
         let triangle_data = (&triangles, &colors)
             .join()
             .flat_map(|(triangle, color)| triangle.get_vertices(color))
+            .chain(
+                (&rectangles, &colors)
+                    .join()
+                    .flat_map(|(rectangle, color)| rectangle.get_vertices(color)),
+            )
             .collect::<Box<[TriangleArgs]>>();
 
         //Update vertex count and see if it has changed
@@ -371,6 +379,8 @@ pub struct CircleArgs {
     pub color: vec4,
     /// Represents whether it is top/bottom left/right, using +1/-1
     pub rel: vec2,
+    /// Fraction of the radius that is not drawn, for rings
+    pub inner: float,
 }
 
 /// Required to send data into the shader.
@@ -384,6 +394,8 @@ impl AsVertex for CircleArgs {
             (Format::Rgba32Sfloat, "color"),
             // vec2 rel;
             (Format::Rg32Sfloat, "rel"),
+            // float inner;
+            (Format::R32Sfloat, "inner"),
         ))
     }
 }
@@ -398,9 +410,15 @@ fn transform_point(p: &Point2<f32>) -> vec2 {
 
 impl Circle {
     /// Helper function to convert triangle into 3 vertices
-    pub fn get_vertices(&self, t: &Transform, color: &Color) -> Vec<CircleArgs> {
+    pub fn get_vertices(
+        &self,
+        t: &Transform,
+        color: &Color,
+        ring: Option<&Ring>,
+    ) -> Vec<CircleArgs> {
         let (c, r) = (t.0, self.radius);
         let color = color.inner().into();
+        let inner = ring.map_or(0., |ring| ring.0);
         let square = vec![
             Vector2::new(-1., -1.),
             Vector2::new(-1., 1.),
@@ -419,6 +437,7 @@ impl Circle {
                     pos: transform_point(&p),
                     color,
                     rel: [rel.x, rel.y].into(),
+                    inner,
                 }
             })
             .collect()
@@ -459,3 +478,15 @@ impl Triangle {
             .collect()
     }
 }
+
+impl Rectangle {
+    pub fn get_vertices(&self, color: &Color) -> Vec<TriangleArgs> {
+        let (a, c) = (self.min, self.max);
+        let (b, d) = (Point2::new(a.x, c.y), Point2::new(c.x, a.y));
+        Triangle::new(a, b, c)
+            .get_vertices(color)
+            .into_iter()
+            .chain(Triangle::new(a, c, d).get_vertices(color))
+            .collect()
+    }
+}
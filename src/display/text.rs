@@ -0,0 +1,56 @@
+//! Helpers to show text on screen, using amethyst's UI
+
+use amethyst::{
+    assets::{AssetStorage, Loader},
+    ecs::Entity,
+    prelude::*,
+    ui::{get_default_font, Anchor, FontAsset, FontHandle, LineMode, UiText, UiTransform},
+};
+
+pub fn default_font(world: &World) -> FontHandle {
+    get_default_font(
+        &*world.read_resource::<Loader>(),
+        &world.read_resource::<AssetStorage<FontAsset>>(),
+    )
+}
+
+/// Components for a line of text placed at (`x`, `y`) from `anchor`, which
+/// is also the side the text is aligned to. Positive y is up.
+pub fn text_components(
+    font: &FontHandle,
+    text: String,
+    anchor: Anchor,
+    (x, y): (f32, f32),
+    font_size: f32,
+    color: [f32; 4],
+) -> (UiTransform, UiText) {
+    let transform = UiTransform::new(
+        text.clone(),
+        anchor,
+        anchor,
+        x,
+        y,
+        1.,
+        1000.,
+        font_size * 1.5,
+    );
+    let mut ui_text = UiText::new(font.clone(), text, color, font_size);
+    ui_text.line_mode = LineMode::Single;
+    ui_text.align = anchor;
+    (transform, ui_text)
+}
+
+/// Creates a line of text horizontally centered on the screen. `y` is the
+/// offset from the center of the screen, positive is up.
+pub fn create_text(
+    world: &mut World,
+    font: &FontHandle,
+    text: String,
+    y: f32,
+    font_size: f32,
+    color: [f32; 4],
+) -> Entity {
+    let (transform, ui_text) =
+        text_components(font, text, Anchor::Middle, (0., y), font_size, color);
+    world.create_entity().with(transform).with(ui_text).build()
+}
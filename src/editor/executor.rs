@@ -2,7 +2,10 @@ use amethyst::{
     core::math::{Point2, RealField, Rotation2, Vector2},
     core::timing::Time,
     derive::SystemDesc,
-    ecs::{world::Builder, Component, Entities, LazyUpdate, Read, ReadStorage, System, SystemData},
+    ecs::{
+        world::Builder, Component, Entities, Entity, Join, LazyUpdate, Read, ReadStorage, System,
+        SystemData, Write, WriteStorage,
+    },
     prelude::*,
 };
 
@@ -13,17 +16,21 @@ use std::time::Duration;
 use crate::{
     components::{
         enemy_spawner::{EnemySpawner, EnemySpawnerLogic, SpawnSpeed},
-        Circle, Color, Moving, Transform, Triangle,
+        living_players,
+        pickup::PICKUP_SPEED,
+        Boss, Circle, Color, Moving, Orbit, Pickup, Player, Ring, Scripted, Transform, Triangle,
     },
+    difficulty::Difficulty,
     display::{HEIGHT, WIDTH},
     editor::{
         reader::{
-            lua::LuaLevel, BallEnemy, Formation, HorizontalLinePlacement, HorizontalLineSide,
-            Level, LevelEvent, VerticalLinePlacement, VerticalLineSide,
+            lua::LuaLevel, BallEnemy, FollowPlayer, Formation, HorizontalLinePlacement,
+            HorizontalLineSide, Level, LevelEvent, SatelliteFate, Satellites,
+            VerticalLinePlacement, VerticalLineSide,
         },
         Vec2,
     },
-    systems::player::movement::PlayerPosition,
+    systems::gameplay::play_area::PlayArea,
     utils::{creator::LazyCreator, fs::root},
 };
 
@@ -35,10 +42,25 @@ enum State {
     Sleeping { until: Duration },
     /// Sleep while there are enemies on screen
     WaitUntilNoEnemies,
+    /// Sleep until the boss leaves the given phase, by going to the next one
+    /// or dying
+    WaitBossPhase { phase: u8 },
     /// Level execution is over
     Finished,
 }
 
+/// Whether the level is complete, that is, all of its events were executed
+/// and there are no enemies left
+#[derive(Debug, Default)]
+pub struct LevelProgress {
+    pub complete: bool,
+    /// Level events read so far, including skipped ones
+    pub events: usize,
+    /// Whether a checkpoint was reached, not counting the one skipping
+    /// stopped at
+    pub checkpoint_reached: bool,
+}
+
 /// This system reads a level (usually from a lua file) and executes all of its
 /// commands.
 #[derive(SystemDesc)]
@@ -46,20 +68,37 @@ pub struct LevelExecutorSystem<L: Level> {
     level: L,
     state: State,
     indicator_duration: f64,
+    boss_phase: Option<u8>,
+    difficulty: Difficulty,
+    /// Events to fast forward through before playing
+    skip: usize,
 }
 
 impl LevelExecutorSystem<LuaLevel> {
-    pub fn from_lua(level_name: &str) -> Self {
-        let level = LuaLevel::new(&root().join(format!("levels/{}.lua", level_name)))
+    pub fn from_lua(level_name: &str, difficulty: Difficulty) -> Self {
+        let mut level = LuaLevel::new(&root().join(format!("levels/{}.lua", level_name)))
             .expect("Failed to load level");
+        level.set_difficulty(difficulty);
         Self {
             level,
             state: State::ReadyForInstruction,
             indicator_duration: 1.,
+            boss_phase: None,
+            difficulty,
+            skip: 0,
         }
     }
 }
 
+impl<L: Level> LevelExecutorSystem<L> {
+    /// Starts the level after its first `events` events, for practice mode.
+    /// Only the events that change settings are executed while skipping.
+    pub fn skipping(mut self, events: usize) -> Self {
+        self.skip = events;
+        self
+    }
+}
+
 impl<'s, L: Level> System<'s> for LevelExecutorSystem<L> {
     type SystemData = (
         Read<'s, Time>,
@@ -67,19 +106,31 @@ impl<'s, L: Level> System<'s> for LevelExecutorSystem<L> {
         Read<'s, LazyUpdate>,
         ReadStorage<'s, BallEnemy>,
         ReadStorage<'s, EnemySpawner>,
-        Read<'s, PlayerPosition>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Boss>,
+        WriteStorage<'s, Scripted>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, Moving>,
+        Write<'s, LevelProgress>,
     );
 
     fn setup(&mut self, world: &mut World) {
         world.register::<EnemySpawner>();
     }
 
-    fn run(&mut self, data: Self::SystemData) {
+    fn run(&mut self, mut data: Self::SystemData) {
+        self.run_behaviours(&mut data);
         let time = &data.0;
+        self.update_boss_phase(&data.6);
         loop {
             match self.state {
-                // Finished -- do nothing
-                State::Finished => return,
+                // Finished -- wait for the enemies to be gone
+                State::Finished => {
+                    if data.3.is_empty() && data.4.is_empty() {
+                        data.10.complete = true;
+                    }
+                    return;
+                }
                 // Sleeping -- either continue or execute the next instruction
                 State::Sleeping { until } => {
                     if time.absolute_time() >= until {
@@ -98,14 +149,40 @@ impl<'s, L: Level> System<'s> for LevelExecutorSystem<L> {
                         return;
                     }
                 }
+                // WaitBossPhase -- continue waiting if the boss phase hasn't changed
+                State::WaitBossPhase { phase } => {
+                    if self.boss_phase != Some(phase) {
+                        self.state = State::ReadyForInstruction;
+                    } else {
+                        return;
+                    }
+                }
                 // ReadyForInstruction - execute an instruction
                 State::ReadyForInstruction => {
                     let event = self.level.next();
                     debug!("Got event: {:?}", event);
+                    data.10.events += 1;
+                    if data.10.events <= self.skip
+                        && !matches!(
+                            event,
+                            None | Some(LevelEvent::SetDefaultIndicatorDuration(_))
+                                | Some(LevelEvent::SetPlayArea { .. })
+                        )
+                    {
+                        continue;
+                    }
+                    if matches!(event, Some(LevelEvent::Checkpoint(_)))
+                        && data.10.events > self.skip + 1
+                    {
+                        data.10.checkpoint_reached = true;
+                    }
                     self.state = self.handle_level_event(event, &data);
                     // Exit. It may be the case that an enemy was created lazily so we
                     // need to wait for the next iteration to be sure.
-                    if matches!(self.state, State::WaitUntilNoEnemies) {
+                    if matches!(
+                        self.state,
+                        State::WaitUntilNoEnemies | State::WaitBossPhase { .. }
+                    ) {
                         return;
                     }
                 }
@@ -204,16 +281,26 @@ impl From<VerticalLinePlacement> for HorizontalLinePlacement {
 struct SingleSpawnerLogic {
     enemy: BallEnemy,
     radius: f32,
+    satellites: Option<Satellites>,
 }
 
 impl EnemySpawnerLogic for SingleSpawnerLogic {
-    fn do_spawn(&self, creator: &LazyCreator, pos: Point2<f32>, speed: Vector2<f32>) {
-        creator.create_enemy(
+    fn do_spawn(
+        &self,
+        creator: &LazyCreator,
+        pos: Point2<f32>,
+        speed: Vector2<f32>,
+    ) -> Vec<Entity> {
+        let parent = creator.create_enemy(
             self.enemy,
             Circle::with_radius(self.radius),
             Transform::from(pos),
             Moving::from(speed),
         );
+        if let Some(satellites) = &self.satellites {
+            creator.create_satellites(parent, pos, speed, satellites);
+        }
+        vec![parent]
     }
 }
 
@@ -226,33 +313,93 @@ struct MultipleSpawnerLogic {
 }
 
 impl EnemySpawnerLogic for MultipleSpawnerLogic {
-    fn do_spawn(&self, creator: &LazyCreator, pos: Point2<f32>, speed: Vector2<f32>) {
+    fn do_spawn(
+        &self,
+        creator: &LazyCreator,
+        pos: Point2<f32>,
+        speed: Vector2<f32>,
+    ) -> Vec<Entity> {
         let mut enemies = self.enemies.iter().cycle();
         let dir = Vector2::from(speed).normalize();
-        for i in 0..self.amount {
-            creator.create_enemy(
-                enemies.next().unwrap().clone(),
-                Circle::with_radius(self.radius),
-                Transform::from(pos - dir * (i as f32) * (self.spacing + 2. * self.radius)),
-                Moving::from(speed),
-            );
-        }
+        (0..self.amount)
+            .map(|i| {
+                creator.create_enemy(
+                    enemies.next().unwrap().clone(),
+                    Circle::with_radius(self.radius),
+                    Transform::from(pos - dir * (i as f32) * (self.spacing + 2. * self.radius)),
+                    Moving::from(speed),
+                )
+            })
+            .collect()
+    }
+}
+
+impl Satellites {
+    /// Scales the size and speed of the satellites and their orbits
+    fn with_difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.radius *= difficulty.enemy_radius();
+        self.orbit_radius *= difficulty.enemy_radius();
+        self.angular_speed *= difficulty.enemy_speed();
+        self
     }
 }
 
 impl<'s> Formation {
-    fn get_spawners(self) -> Vec<EnemySpawner> {
-        match self {
+    /// Scales the speed and radius of the enemies
+    fn with_difficulty(mut self, difficulty: Difficulty) -> Self {
+        let (k_speed, k_radius) = (difficulty.enemy_speed(), difficulty.enemy_radius());
+        match &mut self {
+            Formation::Single {
+                speed,
+                radius,
+                satellites,
+                ..
+            } => {
+                *speed = Vec2(speed.0 * k_speed, speed.1 * k_speed);
+                *radius *= k_radius;
+                *satellites = satellites.take().map(|s| s.with_difficulty(difficulty));
+            }
+            Formation::Multiple { speed, radius, .. } => {
+                *speed = Vec2(speed.0 * k_speed, speed.1 * k_speed);
+                *radius *= k_radius;
+            }
+            Formation::VerticalLine { speed, radius, .. }
+            | Formation::HorizontalLine { speed, radius, .. }
+            | Formation::Circle {
+                speed,
+                enemy_radius: radius,
+                ..
+            }
+            | Formation::Spiral {
+                speed,
+                enemy_radius: radius,
+                ..
+            } => {
+                *speed *= k_speed;
+                *radius *= k_radius;
+            }
+        }
+        self
+    }
+
+    fn get_spawners(self, difficulty: Difficulty) -> Vec<EnemySpawner> {
+        match self.with_difficulty(difficulty) {
             Formation::Single {
                 enemy,
                 pos,
                 speed,
                 radius,
+                satellites,
             } => vec![EnemySpawner {
                 position: pos.into(),
                 spawn_speed: SpawnSpeed::Fixed(speed.into()),
-                logic: Box::new(SingleSpawnerLogic { enemy, radius }),
+                logic: Box::new(SingleSpawnerLogic {
+                    enemy,
+                    radius,
+                    satellites,
+                }),
                 spawn_at: 0.,
+                behaviour: None,
             }],
             Formation::Multiple {
                 enemies,
@@ -271,6 +418,7 @@ impl<'s> Formation {
                     radius,
                 }),
                 spawn_at: 0.,
+                behaviour: None,
             }],
             Formation::VerticalLine {
                 enemies,
@@ -294,8 +442,10 @@ impl<'s> Formation {
                         logic: Box::new(SingleSpawnerLogic {
                             enemy: enemies.next().unwrap(),
                             radius,
+                            satellites: None,
                         }),
                         spawn_at: 0.,
+                        behaviour: None,
                     })
                     .collect()
             }
@@ -321,8 +471,10 @@ impl<'s> Formation {
                         logic: Box::new(SingleSpawnerLogic {
                             enemy: enemies.next().unwrap(),
                             radius,
+                            satellites: None,
                         }),
                         spawn_at: 0.,
+                        behaviour: None,
                     })
                     .collect()
             }
@@ -357,8 +509,10 @@ impl<'s> Formation {
                             logic: Box::new(SingleSpawnerLogic {
                                 enemy: enemies.next().unwrap(),
                                 radius: enemy_radius,
+                                satellites: None,
                             }),
                             spawn_at: 0.,
+                            behaviour: None,
                         }
                     })
                     .collect()
@@ -386,8 +540,10 @@ impl<'s> Formation {
                             logic: Box::new(SingleSpawnerLogic {
                                 enemy: enemies.next().unwrap(),
                                 radius: enemy_radius,
+                                satellites: None,
                             }),
                             spawn_at: 0.,
+                            behaviour: None,
                         }
                     })
                     .collect()
@@ -397,6 +553,66 @@ impl<'s> Formation {
 }
 
 impl<L: Level> LevelExecutorSystem<L> {
+    /// Runs the behaviours of scripted enemies that are due this frame,
+    /// then spawns whatever they asked for.
+    fn run_behaviours(&mut self, data: &mut <Self as System>::SystemData) {
+        let mut spawns = Vec::new();
+        let mut failed = Vec::new();
+        let now = data.0.absolute_time_seconds();
+        // Behaviours see the speeds of Normal, so they work the same on every
+        // difficulty and don't scale the speed again on each run
+        let k_speed = self.difficulty.enemy_speed();
+        for (entity, scripted, transform, moving) in
+            (&data.1, &mut data.7, &mut data.8, &mut data.9).join()
+        {
+            if !scripted.tick(now) {
+                continue;
+            }
+            let result = match self.level.run_behaviour(
+                scripted.behaviour,
+                transform.0.into(),
+                (moving.0 / k_speed).into(),
+            ) {
+                Some(result) => result,
+                // Broken behaviours are dropped, the enemy keeps going as it was
+                None => {
+                    failed.push(entity);
+                    continue;
+                }
+            };
+            if let Some(pos) = result.pos {
+                transform.0 = pos.into();
+            }
+            if let Some(speed) = result.speed {
+                moving.0 = Vector2::from(speed) * k_speed;
+            }
+            spawns.extend(result.spawn);
+        }
+        for entity in failed {
+            data.7.remove(entity);
+        }
+        for formation in spawns {
+            self.handle_level_event(
+                Some(LevelEvent::CustomSpawn {
+                    formation,
+                    indicator_duration: Some(0.),
+                    follow_player: None,
+                    behaviour: None,
+                }),
+                data,
+            );
+        }
+    }
+
+    /// Notifies the level of boss phase changes
+    fn update_boss_phase(&mut self, bosses: &ReadStorage<Boss>) {
+        let phase = bosses.join().next().map(|boss| boss.phase);
+        if phase != self.boss_phase {
+            self.boss_phase = phase;
+            self.level.on_boss_phase(phase);
+        }
+    }
+
     fn handle_level_event(
         &mut self,
         event: Option<LevelEvent>,
@@ -422,7 +638,8 @@ impl<L: Level> LevelExecutorSystem<L> {
                 Some(LevelEvent::CustomSpawn {
                     formation,
                     indicator_duration: None,
-                    follow_player: false,
+                    follow_player: None,
+                    behaviour: None,
                 }),
                 data,
             ),
@@ -431,18 +648,25 @@ impl<L: Level> LevelExecutorSystem<L> {
                 formation,
                 indicator_duration,
                 follow_player,
+                behaviour,
             }) => {
-                let duration = indicator_duration.unwrap_or(self.indicator_duration);
+                let duration = indicator_duration.unwrap_or(self.indicator_duration)
+                    * self.difficulty.indicator_duration();
                 let creator = LazyCreator { lazy, entities };
-                for mut spawner in formation.get_spawners() {
-                    if follow_player {
-                        spawner.spawn_speed = SpawnSpeed::AimAtPlayer {
-                            speed: spawner.calc_speed(Point2::new(0., 0.)).norm(),
-                        };
+                for mut spawner in formation.get_spawners(self.difficulty) {
+                    spawner.behaviour = behaviour;
+                    let speed = spawner.calc_speed(&[]).norm();
+                    match follow_player.unwrap_or(FollowPlayer::No) {
+                        FollowPlayer::No => {}
+                        FollowPlayer::Aim => {
+                            spawner.spawn_speed = SpawnSpeed::AimAtPlayer { speed }
+                        }
+                        FollowPlayer::Intercept => {
+                            spawner.spawn_speed = SpawnSpeed::Intercept { speed }
+                        }
                     }
                     if duration <= 0. {
-                        let player_pos: &PlayerPosition = &data.5;
-                        spawner.do_spawn(&creator, player_pos.0);
+                        spawner.do_spawn(&creator, &living_players(&data.5, &data.8));
                     } else {
                         creator
                             .create_entity()
@@ -458,6 +682,54 @@ impl<L: Level> LevelExecutorSystem<L> {
                 }
                 State::ReadyForInstruction
             }
+            // Create a boss then execute the next event
+            Some(LevelEvent::SpawnBoss {
+                pos,
+                speed,
+                radius,
+                health,
+                phases,
+            }) => {
+                let creator = LazyCreator { lazy, entities };
+                creator.create_boss(
+                    Boss::new(health, phases.unwrap_or_default()),
+                    Circle::with_radius(radius * self.difficulty.enemy_radius()),
+                    pos.into(),
+                    Vector2::from(speed) * self.difficulty.enemy_speed(),
+                );
+                // The boss is created lazily, so let the level know right away
+                self.boss_phase = Some(1);
+                self.level.on_boss_phase(self.boss_phase);
+                State::ReadyForInstruction
+            }
+            // Create a pickup then execute the next event
+            Some(LevelEvent::SpawnPickup { pickup, pos, speed }) => {
+                LazyCreator { lazy, entities }.create_pickup(
+                    pickup,
+                    pos.into(),
+                    speed.map_or(Vector2::new(0., PICKUP_SPEED), Vector2::from),
+                );
+                State::ReadyForInstruction
+            }
+            // Sleep until the boss goes to the next phase, if there's still a boss
+            Some(LevelEvent::WaitBossPhase()) => match self.boss_phase {
+                Some(phase) => State::WaitBossPhase { phase },
+                None => State::ReadyForInstruction,
+            },
+            // Change where the player can move
+            Some(LevelEvent::SetPlayArea { pos, size }) => {
+                let min = Point2::from(pos);
+                let area = PlayArea {
+                    min,
+                    max: min + Vector2::from(size),
+                };
+                lazy.exec_mut(move |world| *world.write_resource::<PlayArea>() = area);
+                State::ReadyForInstruction
+            }
+            Some(LevelEvent::Checkpoint(name)) => {
+                debug!("Reached checkpoint {}", name);
+                State::ReadyForInstruction
+            }
         }
     }
 }
@@ -483,6 +755,9 @@ pub mod tests {
                 level: EmptyLevel,
                 state: State::ReadyForInstruction,
                 indicator_duration: 0.,
+                boss_phase: None,
+                difficulty: Difficulty::Normal,
+                skip: 0,
             }
         }
         pub fn test_handle_event(&mut self, event: LevelEvent, world: &mut World) {
@@ -520,31 +795,39 @@ pub mod tests {
     pub fn get_world() -> World {
         let mut world = World::new();
         world.insert(Time::default());
-        world.insert(PlayerPosition::default());
-        register!(Transform, Circle, Color, Moving, BallEnemy, EnemySpawner, Triangle -> world);
+        world.insert(LevelProgress::default());
+        world.insert(PlayArea::default());
+        register!(Transform, Circle, Color, Moving, BallEnemy, EnemySpawner, Triangle, Orbit, Boss,
+            Scripted, Pickup, Ring, Player -> world);
         world
     }
 
-    #[test]
-    fn test_create_single() {
-        let mut world = get_world();
-        let spawners = Formation::Single {
-            enemy: BallEnemy::Simple,
-            pos: Vec2(0., 0.),
-            speed: Vec2(10., 0.),
-            radius: 10.,
-        }
-        .get_spawners();
+    /// Spawns the enemies of `spawners` right away, with no players to aim at
+    fn spawn_all(world: &mut World, spawners: Vec<EnemySpawner>) {
         for spawner in spawners {
             spawner.do_spawn(
                 &LazyCreator {
                     lazy: &world.fetch(),
                     entities: &world.fetch(),
                 },
-                Point2::new(0., 0.),
+                &[],
             );
         }
         world.maintain();
+    }
+
+    #[test]
+    fn test_create_single() {
+        let mut world = get_world();
+        let spawners = Formation::Single {
+            enemy: BallEnemy::Simple,
+            pos: Vec2(0., 0.),
+            speed: Vec2(10., 0.),
+            radius: 10.,
+            satellites: None,
+        }
+        .get_spawners(Difficulty::Normal);
+        spawn_all(&mut world, spawners);
         let (ts, cs, ms, es) = (
             world.read_storage::<Transform>(),
             world.read_storage::<Circle>(),
@@ -561,4 +844,344 @@ pub mod tests {
         assert_eq!(m.0, Vector2::new(10., 0.));
         assert_eq!(matches!(e, BallEnemy::Simple), true);
     }
+
+    #[test]
+    fn test_difficulty_scales_formations() {
+        let mut world = get_world();
+        let hard = Difficulty::Hard;
+        let spawners = Formation::Single {
+            enemy: BallEnemy::Simple,
+            pos: Vec2(0., 0.),
+            speed: Vec2(10., 0.),
+            radius: 10.,
+            satellites: None,
+        }
+        .get_spawners(hard);
+        spawn_all(&mut world, spawners);
+        let (cs, ms) = (
+            world.read_storage::<Circle>(),
+            world.read_storage::<Moving>(),
+        );
+        let all = (&cs, &ms).join().collect::<Vec<_>>();
+        assert_eq!(all.len(), 1);
+        let (c, m) = all[0];
+        assert_eq!(c.radius, 10. * hard.enemy_radius());
+        assert_eq!(m.0, Vector2::new(10. * hard.enemy_speed(), 0.));
+    }
+
+    #[test]
+    fn test_difficulty_scales_satellites_and_bosses() {
+        let mut world = get_world();
+        let hard = Difficulty::Hard;
+        let spawners = Formation::Single {
+            enemy: BallEnemy::Simple,
+            pos: Vec2(100., 100.),
+            speed: Vec2(0., 0.),
+            radius: 20.,
+            satellites: Some(Satellites {
+                enemy: BallEnemy::Simple,
+                amount: 1,
+                radius: 5.,
+                orbit_radius: 50.,
+                angular_speed: 1.,
+                on_parent_death: None,
+            }),
+        }
+        .get_spawners(hard);
+        spawn_all(&mut world, spawners);
+        let mut executor = LevelExecutorSystem::new_test();
+        executor.difficulty = hard;
+        executor.test_handle_event(spawn_boss(), &mut world);
+        let (cs, os, bs) = (
+            world.read_storage::<Circle>(),
+            world.read_storage::<Orbit>(),
+            world.read_storage::<Boss>(),
+        );
+        let (c, orbit) = (&cs, &os).join().next().unwrap();
+        assert_eq!(c.radius, 5. * hard.enemy_radius());
+        assert_eq!(orbit.radius, 50. * hard.enemy_radius());
+        assert_eq!(orbit.angular_speed, hard.enemy_speed());
+        let (c, _) = (&cs, &bs).join().next().unwrap();
+        assert_eq!(c.radius, 80. * hard.enemy_radius());
+    }
+
+    #[test]
+    fn test_create_single_with_satellites() {
+        let mut world = get_world();
+        let spawners = Formation::Single {
+            enemy: BallEnemy::Double,
+            pos: Vec2(100., 100.),
+            speed: Vec2(0., 0.),
+            radius: 20.,
+            satellites: Some(Satellites {
+                enemy: BallEnemy::Simple,
+                amount: 4,
+                radius: 5.,
+                orbit_radius: 50.,
+                angular_speed: 0.1,
+                on_parent_death: Some(SatelliteFate::Destroy),
+            }),
+        }
+        .get_spawners(Difficulty::Normal);
+        spawn_all(&mut world, spawners);
+        let (ts, os, es) = (
+            world.read_storage::<Transform>(),
+            world.read_storage::<Orbit>(),
+            world.read_storage::<BallEnemy>(),
+        );
+        assert_eq!((&es, &ts).join().count(), 5);
+        let satellites = (&es, &ts, &os).join().collect::<Vec<_>>();
+        assert_eq!(satellites.len(), 4);
+        for (_, t, orbit) in satellites {
+            assert!(((t.0 - Point2::new(100., 100.)).norm() - 50.).abs() < 1e-3);
+            assert_eq!(orbit.fate, SatelliteFate::Destroy);
+            assert!(ts.get(orbit.parent).is_some());
+        }
+    }
+
+    #[test]
+    fn test_boss_phases() {
+        let mut world = get_world();
+        let mut executor = LevelExecutorSystem::new_test();
+        executor.test_handle_event(
+            LevelEvent::SpawnBoss {
+                pos: Vec2(100., 100.),
+                speed: Vec2(0., 0.),
+                radius: 80.,
+                health: 10,
+                phases: Some(vec![0.3, 0.75]),
+            },
+            &mut world,
+        );
+        assert_eq!(executor.boss_phase, Some(1));
+        let mut bosses = world.write_storage::<Boss>();
+        let boss = (&mut bosses).join().next().unwrap();
+        assert_eq!(boss.thresholds, vec![0.75, 0.3]);
+        assert!(!boss.damage(2));
+        assert_eq!(boss.phase, 1);
+        assert!(!boss.damage(1));
+        assert_eq!(boss.phase, 2);
+        assert!(!boss.damage(4));
+        assert_eq!(boss.phase, 3);
+        assert!(boss.damage(5));
+        assert_eq!(boss.health, 0);
+    }
+
+    /// A level that plays a fixed list of events
+    struct EventsLevel(std::vec::IntoIter<LevelEvent>);
+
+    impl Level for EventsLevel {}
+    impl Iterator for EventsLevel {
+        type Item = LevelEvent;
+        fn next(&mut self) -> Option<LevelEvent> {
+            self.0.next()
+        }
+    }
+
+    impl LevelExecutorSystem<EventsLevel> {
+        fn with_events(events: Vec<LevelEvent>) -> Self {
+            Self {
+                level: EventsLevel(events.into_iter()),
+                state: State::ReadyForInstruction,
+                indicator_duration: 0.,
+                boss_phase: None,
+                difficulty: Difficulty::Normal,
+                skip: 0,
+            }
+        }
+    }
+
+    fn single(x: f32) -> LevelEvent {
+        LevelEvent::Spawn(Formation::Single {
+            enemy: BallEnemy::Simple,
+            pos: Vec2(x, 100.),
+            speed: Vec2(0., 0.),
+            radius: 10.,
+            satellites: None,
+        })
+    }
+
+    fn spawn_boss() -> LevelEvent {
+        LevelEvent::SpawnBoss {
+            pos: Vec2(100., 100.),
+            speed: Vec2(0., 0.),
+            radius: 80.,
+            health: 10,
+            phases: None,
+        }
+    }
+
+    /// Positions of the enemies that aren't bosses
+    fn enemy_positions(world: &World) -> Vec<Point2<f32>> {
+        let (transforms, enemies, bosses) = (
+            world.read_storage::<Transform>(),
+            world.read_storage::<BallEnemy>(),
+            world.read_storage::<Boss>(),
+        );
+        (&transforms, &enemies, !&bosses)
+            .join()
+            .map(|(t, ..)| t.0)
+            .collect()
+    }
+
+    fn kill_bosses(world: &mut World) {
+        let bosses = (&world.entities(), &world.read_storage::<Boss>())
+            .join()
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+        world.delete_entities(&bosses).unwrap();
+        world.maintain();
+    }
+
+    #[test]
+    fn test_wait_boss_phase_without_boss() {
+        let mut world = get_world();
+        let mut executor =
+            LevelExecutorSystem::with_events(vec![LevelEvent::WaitBossPhase(), single(100.)]);
+        executor.run(world.system_data());
+        world.maintain();
+        assert_eq!(enemy_positions(&world), vec![Point2::new(100., 100.)]);
+    }
+
+    #[test]
+    fn test_wait_boss_phase_after_boss_died() {
+        let mut world = get_world();
+        let mut executor = LevelExecutorSystem::with_events(vec![
+            spawn_boss(),
+            LevelEvent::WaitUntilNoEnemies(),
+            LevelEvent::WaitBossPhase(),
+            single(100.),
+        ]);
+        executor.run(world.system_data());
+        world.maintain();
+        // The boss dies while the level is waiting for something else
+        kill_bosses(&mut world);
+        executor.run(world.system_data());
+        world.maintain();
+        assert_eq!(executor.boss_phase, None);
+        assert_eq!(enemy_positions(&world), vec![Point2::new(100., 100.)]);
+    }
+
+    #[test]
+    fn test_wait_boss_phase_until_boss_dies() {
+        let mut world = get_world();
+        let mut executor = LevelExecutorSystem::with_events(vec![
+            spawn_boss(),
+            LevelEvent::WaitBossPhase(),
+            single(100.),
+        ]);
+        executor.run(world.system_data());
+        world.maintain();
+        executor.run(world.system_data());
+        world.maintain();
+        assert!(enemy_positions(&world).is_empty());
+        kill_bosses(&mut world);
+        executor.run(world.system_data());
+        world.maintain();
+        assert_eq!(enemy_positions(&world), vec![Point2::new(100., 100.)]);
+    }
+
+    #[test]
+    fn test_skip_events() {
+        let mut world = get_world();
+        let events = vec![
+            single(100.),
+            LevelEvent::SetPlayArea {
+                pos: Vec2(10., 10.),
+                size: Vec2(100., 100.),
+            },
+            LevelEvent::Checkpoint("Test".to_owned()),
+            single(200.),
+        ];
+        let mut executor = LevelExecutorSystem::with_events(events).skipping(3);
+        executor.run(world.system_data());
+        world.maintain();
+        // Only the spawn after the checkpoint happened, but the play area was still set
+        let transforms = world.read_storage::<Transform>();
+        let enemies = world.read_storage::<BallEnemy>();
+        let all = (&transforms, &enemies).join().collect::<Vec<_>>();
+        assert_eq!(all.len(), 1);
+        let (t, _) = all[0];
+        assert_eq!(t.0, Point2::new(200., 100.));
+        assert_eq!(world.read_resource::<PlayArea>().min, Point2::new(10., 10.));
+        assert_eq!(world.read_resource::<LevelProgress>().events, 5);
+    }
+
+    #[test]
+    fn test_checkpoint_reached() {
+        let checkpoint = |name: &str| LevelEvent::Checkpoint(name.to_owned());
+        let reached = |events| {
+            let mut world = get_world();
+            // Starting at the first checkpoint, like practice does
+            let mut executor = LevelExecutorSystem::with_events(events).skipping(1);
+            executor.run(world.system_data());
+            let progress = world.read_resource::<LevelProgress>();
+            progress.checkpoint_reached
+        };
+        assert!(!reached(vec![single(100.), checkpoint("A"), single(200.)]));
+        assert!(reached(vec![
+            single(100.),
+            checkpoint("A"),
+            single(200.),
+            checkpoint("B"),
+        ]));
+    }
+
+    #[test]
+    fn test_set_play_area() {
+        let mut world = get_world();
+        let mut executor = LevelExecutorSystem::new_test();
+        executor.test_handle_event(
+            LevelEvent::SetPlayArea {
+                pos: Vec2(100., 200.),
+                size: Vec2(300., 400.),
+            },
+            &mut world,
+        );
+        let area = world.read_resource::<PlayArea>();
+        assert_eq!(area.min, Point2::new(100., 200.));
+        assert_eq!(area.max, Point2::new(400., 600.));
+    }
+
+    #[test]
+    fn test_spawn_pickup() {
+        let mut world = get_world();
+        let mut executor = LevelExecutorSystem::new_test();
+        executor.test_handle_event(
+            LevelEvent::SpawnPickup {
+                pickup: Pickup::Shield,
+                pos: Vec2(100., 0.),
+                speed: None,
+            },
+            &mut world,
+        );
+        let (pickups, transforms, movings) = (
+            world.read_storage::<Pickup>(),
+            world.read_storage::<Transform>(),
+            world.read_storage::<Moving>(),
+        );
+        let all = (&pickups, &transforms, &movings).join().collect::<Vec<_>>();
+        assert_eq!(all.len(), 1);
+        let (pickup, transform, moving) = all[0];
+        assert_eq!(*pickup, Pickup::Shield);
+        assert_eq!(transform.0, Point2::new(100., 0.));
+        assert_eq!(moving.0, Vector2::new(0., PICKUP_SPEED));
+    }
+
+    #[test]
+    fn test_wait_follows_time_scale() {
+        let mut world = get_world();
+        let mut executor = LevelExecutorSystem::new_test();
+        world.write_resource::<Time>().set_time_scale(0.5);
+        let state = executor.handle_level_event(Some(LevelEvent::Wait(1.)), &world.system_data());
+        let until = match state {
+            State::Sleeping { until } => until,
+            _ => panic!("Waiting should sleep"),
+        };
+        // A real second is only half a second of game time
+        world.write_resource::<Time>().set_delta_seconds(1.);
+        assert!(world.read_resource::<Time>().absolute_time() < until);
+        world.write_resource::<Time>().set_delta_seconds(1.);
+        assert!(world.read_resource::<Time>().absolute_time() >= until);
+    }
 }
@@ -1,11 +1,17 @@
 use crate::{
+    difficulty::Difficulty,
     display::{HEIGHT, WIDTH},
-    editor::reader::{
-        BallEnemy, Formation, HorizontalLinePlacement, HorizontalLineSide, Level, LevelEvent,
-        VerticalLinePlacement, VerticalLineSide,
+    editor::{
+        reader::{
+            BallEnemy, Behaviour, BehaviourResult, Formation, HorizontalLinePlacement,
+            HorizontalLineSide, Level, LevelEvent, Pickup, SatelliteFate, Satellites,
+            VerticalLinePlacement, VerticalLineSide,
+        },
+        Vec2,
     },
 };
 use failure::{bail, Error, ResultExt};
+use log::error;
 use rlua::{Context, Function, Lua, RegistryKey, Result as LuaResult, Table, Thread, ThreadStatus};
 use rlua_builders::LuaBuilder;
 use std::{fs, iter::Iterator, path::Path};
@@ -17,6 +23,26 @@ fn create_level_event(ctx: Context) -> LuaResult<Table> {
         .call::<_, Table>(t)
 }
 
+/// Registry key of the table holding all behaviour functions, indexed by id
+const BEHAVIOURS: &str = "behaviours";
+
+/// Creates the `Behaviour` function, that receives a table with a `run` function
+/// and an optional `every`, and returns a `Behaviour` that can be attached to spawns.
+/// `run` receives the enemy position and speed, and may return a table with new
+/// `pos`, `speed` and a list of formations to `spawn`. It must not call LevelEvent functions.
+fn create_behaviour(ctx: Context) -> LuaResult<Function> {
+    ctx.set_named_registry_value(BEHAVIOURS, ctx.create_table()?)?;
+    ctx.create_function(|ctx, t: Table| {
+        let behaviours: Table = ctx.named_registry_value(BEHAVIOURS)?;
+        let id = behaviours.raw_len() as u32 + 1;
+        behaviours.raw_set(id, t.get::<_, Function>("run")?)?;
+        Ok(Behaviour {
+            id,
+            every: t.get::<_, Option<f32>>("every")?.unwrap_or(0.).max(0.),
+        })
+    })
+}
+
 pub struct LuaLevel {
     lua: Lua,
     level_thread: RegistryKey,
@@ -34,10 +60,20 @@ impl LuaLevel {
         let level_thread = lua.context::<_, Result<RegistryKey, Error>>(|ctx| {
             let globals = ctx.globals();
             globals.set("LevelEvent", create_level_event(ctx)?)?;
+            globals.set("Behaviour", create_behaviour(ctx)?)?;
+            globals.set(
+                "FollowPlayer",
+                ctx.create_table_from(vec![("Aim", "Aim"), ("Intercept", "Intercept")])?,
+            )?;
             copy_builders!(
                 BallEnemy, Formation, VerticalLinePlacement, VerticalLineSide,
-                HorizontalLinePlacement, HorizontalLineSide -> ctx
+                HorizontalLinePlacement, HorizontalLineSide, Satellites, SatelliteFate, Pickup -> ctx
             );
+            globals.set(
+                "Difficulty",
+                ctx.create_table_from(Difficulty::ALL.iter().map(|d| (d.name(), d.name())))?,
+            )?;
+            globals.set("DIFFICULTY", Difficulty::default().name())?;
             globals.set("WIDTH", WIDTH)?;
             globals.set("HEIGHT", HEIGHT)?;
 
@@ -52,6 +88,14 @@ impl LuaLevel {
         })?;
         Ok(Self { lua, level_thread })
     }
+
+    /// Lets the script read the difficulty from the `DIFFICULTY` global, which
+    /// is one of the values of the `Difficulty` table
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.lua
+            .context(|ctx| ctx.globals().set("DIFFICULTY", difficulty.name()))
+            .expect("Failed to set difficulty");
+    }
 }
 
 impl Iterator for LuaLevel {
@@ -74,7 +118,30 @@ impl Iterator for LuaLevel {
     }
 }
 
-impl Level for LuaLevel {}
+impl Level for LuaLevel {
+    fn on_boss_phase(&mut self, phase: Option<u8>) {
+        self.lua
+            .context(|ctx| ctx.globals().set("BOSS_PHASE", phase))
+            .expect("Failed to update boss phase");
+    }
+
+    fn run_behaviour(
+        &mut self,
+        behaviour: Behaviour,
+        pos: Vec2,
+        speed: Vec2,
+    ) -> Option<BehaviourResult> {
+        self.lua
+            .context(|ctx| {
+                let behaviours: Table = ctx.named_registry_value(BEHAVIOURS)?;
+                behaviours
+                    .raw_get::<_, Function>(behaviour.id)?
+                    .call::<_, BehaviourResult>((pos, speed))
+            })
+            .map_err(|e| error!("Lua behaviour {} errored: {}", behaviour.id, e))
+            .ok()
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -85,9 +152,9 @@ mod test {
     #[test]
     fn test_test_level_compiles_and_runs() {
         let mut world = get_world();
-        let level = LuaLevel::new(&Path::new("levels/test.lua")).unwrap();
+        let mut level = LuaLevel::new(&Path::new("levels/test.lua")).unwrap();
         // Test if iterator doesn't crash
-        let events = level.collect::<Vec<_>>();
+        let events = level.by_ref().collect::<Vec<_>>();
         assert!(!events.is_empty());
         let mut executor = LevelExecutorSystem::new_test();
         let mut entities = 0;
@@ -104,4 +171,51 @@ mod test {
         }
         assert!(world.entities().join().count() > 0);
     }
+
+    #[test]
+    fn test_behaviours_run() {
+        let mut level = LuaLevel::new(&Path::new("levels/test.lua")).unwrap();
+        let behaviours = level
+            .by_ref()
+            .filter_map(|ev| match ev {
+                LevelEvent::CustomSpawn { behaviour, .. } => behaviour,
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert!(!behaviours.is_empty());
+        for behaviour in behaviours {
+            assert!(level
+                .run_behaviour(behaviour, Vec2(100., 100.), Vec2(5., 5.))
+                .is_some());
+        }
+    }
+
+    #[test]
+    fn test_behaviour_errors() {
+        let mut level = LuaLevel::new(&Path::new("levels/test.lua")).unwrap();
+        let behaviour = level
+            .lua
+            .context(|ctx| {
+                ctx.load("return Behaviour { run = function() error('typo') end }")
+                    .eval::<Behaviour>()
+            })
+            .unwrap();
+        assert!(level
+            .run_behaviour(behaviour, Vec2(100., 100.), Vec2(5., 5.))
+            .is_none());
+    }
+
+    #[test]
+    fn test_difficulty_global() {
+        let mut level = LuaLevel::new(&Path::new("levels/test.lua")).unwrap();
+        level.set_difficulty(Difficulty::Hard);
+        let hard = level
+            .lua
+            .context(|ctx| {
+                ctx.load("return DIFFICULTY == Difficulty.Hard")
+                    .eval::<bool>()
+            })
+            .unwrap();
+        assert!(hard);
+    }
 }
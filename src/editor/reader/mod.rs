@@ -3,12 +3,15 @@ pub mod lua;
 use std::iter::Iterator;
 
 use crate::editor::Vec2;
+use rlua::{Context, Error, FromLua, Result, Value};
 use rlua_builders::{LuaBuilder, UserData};
 
 #[derive(Debug, Clone, Copy, UserData, LuaBuilder)]
 pub enum BallEnemy {
     Simple,
     Double,
+    /// Can't be killed by shots, but still kills the player
+    Obstacle,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, UserData, LuaBuilder)]
@@ -23,6 +26,110 @@ pub enum HorizontalLineSide {
     Bottom,
 }
 
+/// What happens to satellites when the enemy they orbit dies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UserData, LuaBuilder)]
+pub enum SatelliteFate {
+    /// Satellites keep moving on their own, in the direction they were going
+    Release,
+    /// Satellites die together with their parent
+    Destroy,
+}
+
+/// Items the player can pick up by touching them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, UserData, LuaBuilder)]
+pub enum Pickup {
+    ExtraLife,
+    /// Switches to a random different weapon
+    Weapon,
+    /// Absorbs the next hit
+    Shield,
+    /// Slows down the game for a while
+    SlowMotion,
+}
+
+/// Small enemies that orbit around a parent enemy
+#[derive(Debug, Clone, Copy, UserData, LuaBuilder)]
+pub struct Satellites {
+    pub enemy: BallEnemy,
+    pub amount: u8,
+    #[default = 10.]
+    pub radius: f32,
+    /// Distance from the center of the parent to the center of each satellite
+    #[default = 60.]
+    pub orbit_radius: f32,
+    /// In radians per second. Negative values orbit clockwise.
+    #[default = 3.]
+    pub angular_speed: f32,
+    /// Default is Release
+    pub on_parent_death: Option<SatelliteFate>,
+}
+
+/// Whether spawned enemies should go towards the player. In lua, this is either
+/// a boolean (true meaning Aim) or a value from the `FollowPlayer` table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FollowPlayer {
+    /// Keep the formation's speed
+    No,
+    /// Aim at the player's current position
+    Aim,
+    /// Aim where the player will be if they keep moving the same way
+    Intercept,
+}
+
+impl<'s> FromLua<'s> for FollowPlayer {
+    fn from_lua(val: Value<'s>, _: Context<'s>) -> Result<Self> {
+        match val {
+            Value::Boolean(false) => Ok(FollowPlayer::No),
+            Value::Boolean(true) => Ok(FollowPlayer::Aim),
+            Value::String(s) if s.as_bytes() == b"Aim" => Ok(FollowPlayer::Aim),
+            Value::String(s) if s.as_bytes() == b"Intercept" => Ok(FollowPlayer::Intercept),
+            _ => Err(Error::FromLuaConversionError {
+                from: "value",
+                to: "FollowPlayer",
+                message: Some("Expected a boolean or a FollowPlayer value".to_owned()),
+            }),
+        }
+    }
+}
+
+/// Reference to a lua function that controls enemies, created with `Behaviour`
+/// in the level script
+#[derive(Debug, Clone, Copy, UserData)]
+pub struct Behaviour {
+    pub id: u32,
+    /// Seconds of game time between calls, 0 to call it every frame
+    pub every: f32,
+}
+
+/// What an enemy behaviour returned. Missing values are left unchanged.
+#[derive(Debug, Default)]
+pub struct BehaviourResult {
+    pub pos: Option<Vec2>,
+    pub speed: Option<Vec2>,
+    /// Formations to spawn immediately, without indicators
+    pub spawn: Vec<Formation>,
+}
+
+impl<'s> FromLua<'s> for BehaviourResult {
+    fn from_lua(val: Value<'s>, _: Context<'s>) -> Result<Self> {
+        match val {
+            Value::Nil => Ok(Self::default()),
+            Value::Table(t) => Ok(Self {
+                pos: t.get("pos")?,
+                speed: t.get("speed")?,
+                spawn: t
+                    .get::<_, Option<Vec<Formation>>>("spawn")?
+                    .unwrap_or_default(),
+            }),
+            _ => Err(Error::FromLuaConversionError {
+                from: "value",
+                to: "BehaviourResult",
+                message: Some("Behaviours must return a table or nil".to_owned()),
+            }),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, UserData, LuaBuilder)]
 pub enum VerticalLinePlacement {
     Distribute { margin: Option<f32> },
@@ -47,6 +154,7 @@ pub enum Formation {
         speed: Vec2,
         #[default = 20.]
         radius: f32,
+        satellites: Option<Satellites>,
     },
     Multiple {
         enemies: Vec<BallEnemy>,
@@ -61,7 +169,7 @@ pub enum Formation {
     VerticalLine {
         enemies: Vec<BallEnemy>,
         amount: u8,
-        #[default = 15.]
+        #[default = 900.]
         speed: f32,
         #[default = 20.]
         radius: f32,
@@ -71,7 +179,7 @@ pub enum Formation {
     HorizontalLine {
         enemies: Vec<BallEnemy>,
         amount: u8,
-        #[default = 15.]
+        #[default = 900.]
         speed: f32,
         #[default = 20.]
         radius: f32,
@@ -81,7 +189,7 @@ pub enum Formation {
     Circle {
         enemies: Vec<BallEnemy>,
         amount: u8,
-        #[default = 15.]
+        #[default = 900.]
         speed: f32,
         #[default = 20.]
         enemy_radius: f32,
@@ -97,7 +205,7 @@ pub enum Formation {
         spacing: f32,
         #[default = 20.]
         enemy_radius: f32,
-        #[default = 10.]
+        #[default = 600.]
         speed: f32,
     },
 }
@@ -110,10 +218,49 @@ pub enum LevelEvent {
     CustomSpawn {
         formation: Formation,
         indicator_duration: Option<f64>,
-        #[default = false]
-        follow_player: bool,
+        /// Default is false
+        follow_player: Option<FollowPlayer>,
+        behaviour: Option<Behaviour>,
     },
     SetDefaultIndicatorDuration(f64),
+    SpawnBoss {
+        pos: Vec2,
+        speed: Vec2,
+        #[default = 80.]
+        radius: f32,
+        health: u32,
+        /// Fractions of the total health at which each new phase starts
+        phases: Option<Vec<f32>>,
+    },
+    /// Waits until the boss goes to the next phase or dies
+    WaitBossPhase(),
+    /// Creates a pickup. By default it drifts slowly down.
+    SpawnPickup {
+        pickup: Pickup,
+        pos: Vec2,
+        speed: Option<Vec2>,
+    },
+    /// Restricts the player to a rectangle starting at `pos` (top left corner)
+    SetPlayArea {
+        pos: Vec2,
+        size: Vec2,
+    },
+    /// Marks a point practice mode can start from. Does nothing otherwise.
+    Checkpoint(String),
 }
 
-pub trait Level: Iterator<Item = LevelEvent> {}
+pub trait Level: Iterator<Item = LevelEvent> {
+    /// Called when the boss is spawned, changes phase or dies (with `None`)
+    fn on_boss_phase(&mut self, _phase: Option<u8>) {}
+
+    /// Runs the behaviour of an enemy at `pos` moving with `speed`. Returns
+    /// `None` if it failed, so it shouldn't run again.
+    fn run_behaviour(
+        &mut self,
+        _behaviour: Behaviour,
+        _pos: Vec2,
+        _speed: Vec2,
+    ) -> Option<BehaviourResult> {
+        Some(BehaviourResult::default())
+    }
+}
@@ -1,6 +1,6 @@
 use crate::components::{Moving, Transform};
 use amethyst::core::math::{Point2, Vector2};
-use rlua::{Context, Error, FromLua, Result, Value};
+use rlua::{Context, Error, FromLua, Result, ToLua, Value};
 
 /// Our wrapper for Vector2<f32> and Point2<f32>, since we need but can't
 /// implement some traits on it
@@ -31,6 +31,26 @@ impl From<Vec2> for Moving {
     }
 }
 
+impl<'s> ToLua<'s> for Vec2 {
+    fn to_lua(self, ctx: Context<'s>) -> Result<Value<'s>> {
+        Ok(Value::Table(
+            ctx.create_sequence_from(vec![self.0, self.1])?,
+        ))
+    }
+}
+
+impl From<Point2<f32>> for Vec2 {
+    fn from(p: Point2<f32>) -> Self {
+        Self(p.x, p.y)
+    }
+}
+
+impl From<Vector2<f32>> for Vec2 {
+    fn from(v: Vector2<f32>) -> Self {
+        Self(v.x, v.y)
+    }
+}
+
 impl<'s> FromLua<'s> for Vec2 {
     fn from_lua(val: Value<'s>, _: Context<'s>) -> Result<Self> {
         let err = || {
@@ -0,0 +1,163 @@
+//! Best scores of each level, saved in the user directory
+
+use amethyst::config::Config;
+use failure::Error;
+use log::{info, warn};
+use ron::ser::{to_string_pretty, PrettyConfig};
+use serde::{Deserialize, Serialize};
+use std::{env, fs, path::PathBuf};
+
+use crate::{
+    difficulty::Difficulty,
+    utils::fs::{root, user_dir},
+};
+
+const HIGH_SCORES_FILE: &str = "highscores.ron";
+
+/// Entries kept in each table
+pub const TABLE_SIZE: usize = 10;
+
+/// A score that made it to a table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub name: String,
+    pub score: u64,
+    /// Local date of the run, as YYYY-MM-DD
+    pub date: String,
+    /// Seed of the run's random number generator
+    pub seed: u64,
+}
+
+impl Entry {
+    /// Entry for the local player, dated today
+    pub fn new(score: u64, seed: u64) -> Self {
+        Self {
+            name: player_name(),
+            score,
+            date: chrono::Local::now()
+                .date_naive()
+                .format("%Y-%m-%d")
+                .to_string(),
+            seed,
+        }
+    }
+
+    /// One line of a table, `rank` starting at 0
+    pub fn describe(&self, rank: usize) -> String {
+        format!(
+            "{:>2}. {:<12} {:>8}  {}",
+            rank + 1,
+            self.name,
+            self.score,
+            self.date
+        )
+    }
+}
+
+/// Scores of a level file on a difficulty, sorted from best to worst
+#[derive(Debug, Serialize, Deserialize)]
+struct Table {
+    level: String,
+    /// Hash of the level file, so changed levels get a new table
+    hash: String,
+    difficulty: String,
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct HighScores {
+    tables: Vec<Table>,
+}
+
+impl HighScores {
+    fn path() -> PathBuf {
+        user_dir().join(HIGH_SCORES_FILE)
+    }
+
+    /// Reads the saved scores, which are empty if there's no file yet
+    pub fn load() -> Self {
+        let path = Self::path();
+        if !path.exists() {
+            return Self::default();
+        }
+        Config::load(&path).unwrap_or_else(|e| {
+            warn!("Failed to read high scores from {:?}: {}", path, e);
+            Self::default()
+        })
+    }
+
+    pub fn save(&self) -> Result<(), Error> {
+        let path = Self::path();
+        fs::create_dir_all(user_dir())?;
+        fs::write(&path, to_string_pretty(self, PrettyConfig::default())?)?;
+        info!("Saved high scores to {:?}", path);
+        Ok(())
+    }
+
+    /// Best scores of a level on a difficulty
+    pub fn table(&self, level: &str, difficulty: Difficulty) -> &[Entry] {
+        let (hash, difficulty) = (level_hash(level), difficulty.name());
+        self.tables
+            .iter()
+            .find(|t| t.level == level && t.hash == hash && t.difficulty == difficulty)
+            .map(|t| t.entries.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Adds an entry to the table of a level, returning its rank if it was
+    /// good enough to be kept
+    pub fn insert(&mut self, level: &str, difficulty: Difficulty, entry: Entry) -> Option<usize> {
+        let (hash, difficulty) = (level_hash(level), difficulty.name());
+        let index = match self
+            .tables
+            .iter()
+            .position(|t| t.level == level && t.hash == hash && t.difficulty == difficulty)
+        {
+            Some(index) => index,
+            None => {
+                self.tables.push(Table {
+                    level: level.to_owned(),
+                    hash,
+                    difficulty: difficulty.to_owned(),
+                    entries: Vec::new(),
+                });
+                self.tables.len() - 1
+            }
+        };
+        let entries = &mut self.tables[index].entries;
+        // Ties go to whoever got there first
+        let rank = entries
+            .iter()
+            .position(|e| e.score < entry.score)
+            .unwrap_or_else(|| entries.len());
+        if rank >= TABLE_SIZE {
+            return None;
+        }
+        entries.insert(rank, entry);
+        entries.truncate(TABLE_SIZE);
+        Some(rank)
+    }
+}
+
+/// FNV-1a hash of a level's file, in hex. Empty if the file can't be read.
+pub fn level_hash(level: &str) -> String {
+    match fs::read(root().join(format!("levels/{}.lua", level))) {
+        Ok(bytes) => {
+            let hash = bytes.iter().fold(0xcbf29ce484222325u64, |hash, &b| {
+                (hash ^ b as u64).wrapping_mul(0x100000001b3)
+            });
+            format!("{:016x}", hash)
+        }
+        Err(e) => {
+            warn!("Failed to hash level {}: {}", level, e);
+            String::new()
+        }
+    }
+}
+
+/// Name of the user running the game
+fn player_name() -> String {
+    env::var("USER")
+        .or_else(|_| env::var("USERNAME"))
+        .unwrap_or_else(|_| "Player".to_owned())
+}
@@ -1,16 +1,76 @@
-use amethyst::input::BindingTypes;
-use derive_more::Display;
+use amethyst::{
+    config::Config,
+    input::{BindingTypes, Bindings, InputHandler},
+};
+use log::warn;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
-#[derive(Clone, Debug, Display, Hash, PartialEq, Eq, Serialize, Deserialize)]
+use crate::utils::fs::{root, user_config_file};
+
+/// Name of the bindings file, both shipped and in the user directory
+pub const BINDINGS_FILE: &str = "bindings.ron";
+
+/// Axes for each player, identified by the player id
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AxisBinding {
-    Vertical,
-    Horizontal,
+    Vertical(usize),
+    Horizontal(usize),
+    /// Shooting direction, from a gamepad stick or the arrow keys. Positive is up.
+    AimVertical(usize),
+    AimHorizontal(usize),
+    /// Gamepad stick for `Vertical`, which amethyst can't bind to the same axis
+    /// as the keyboard
+    StickVertical(usize),
+    StickHorizontal(usize),
+    AimStickVertical(usize),
+    AimStickHorizontal(usize),
 }
 
-#[derive(Clone, Debug, Display, Hash, PartialEq, Eq, Serialize, Deserialize)]
+impl AxisBinding {
+    /// The gamepad stick axis read along with this one
+    fn stick(&self) -> Option<AxisBinding> {
+        match *self {
+            AxisBinding::Vertical(id) => Some(AxisBinding::StickVertical(id)),
+            AxisBinding::Horizontal(id) => Some(AxisBinding::StickHorizontal(id)),
+            AxisBinding::AimVertical(id) => Some(AxisBinding::AimStickVertical(id)),
+            AxisBinding::AimHorizontal(id) => Some(AxisBinding::AimStickHorizontal(id)),
+            _ => None,
+        }
+    }
+}
+
+/// Value of an axis from the keyboard or, when no key is held, the gamepad stick
+pub fn axis_value(input: &InputHandler<PsychoBindingTypes>, axis: &AxisBinding) -> f32 {
+    let value = input.axis_value(axis).unwrap_or(0.);
+    match axis.stick() {
+        Some(stick) if value == 0. => input.axis_value(&stick).unwrap_or(0.),
+        _ => value,
+    }
+}
+
+/// Actions for each player, identified by the player id
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ActionBinding {
-    Shoot,
+    Shoot(usize),
+    NextWeapon(usize),
+    PreviousWeapon(usize),
+    Bomb(usize),
+    Dash(usize),
+    /// Spends the psycho meter to slow the game down while held
+    Psycho(usize),
+}
+
+impl fmt::Display for AxisBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl fmt::Display for ActionBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
 }
 
 #[derive(Debug)]
@@ -20,3 +80,76 @@ impl BindingTypes for PsychoBindingTypes {
     type Axis = AxisBinding;
     type Action = ActionBinding;
 }
+
+/// Adds the controls `bindings` doesn't have from `defaults`, so controls added
+/// after the user rebound theirs still work
+fn add_missing(
+    bindings: &mut Bindings<PsychoBindingTypes>,
+    defaults: &Bindings<PsychoBindingTypes>,
+) {
+    for id in defaults.axes() {
+        if bindings.axis(id).is_some() {
+            continue;
+        }
+        let axis = defaults.axis(id).unwrap().clone();
+        if let Err(e) = bindings.insert_axis(id.clone(), axis) {
+            warn!("Leaving {} unbound: {}", id, e);
+        }
+    }
+    for id in defaults.actions() {
+        if bindings.action_bindings(id).next().is_some() {
+            continue;
+        }
+        for combo in defaults.action_bindings(id) {
+            if let Err(e) = bindings.insert_action_binding(id.clone(), combo.to_vec()) {
+                warn!("Leaving {:?} unbound for {}: {}", combo, id, e);
+            }
+        }
+    }
+}
+
+/// Reads the user's bindings if they were rebound, or the shipped ones otherwise
+pub fn load_bindings() -> Bindings<PsychoBindingTypes> {
+    let defaults =
+        Bindings::load(root().join("config").join(BINDINGS_FILE)).expect("Failed to read bindings");
+    let user = user_config_file(BINDINGS_FILE);
+    if user.exists() {
+        match Bindings::load(&user) {
+            Ok(mut bindings) => {
+                add_missing(&mut bindings, &defaults);
+                return bindings;
+            }
+            // Probably saved by an older version, where controls had other names
+            Err(e) => warn!(
+                "Ignoring the rebound controls in {:?}, which can't be read: {}",
+                user, e
+            ),
+        }
+    }
+    defaults
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::MAX_PLAYERS;
+
+    #[test]
+    fn test_shipped_bindings_load() {
+        let bindings =
+            Bindings::<PsychoBindingTypes>::load(root().join("config").join(BINDINGS_FILE))
+                .expect("Shipped bindings don't load");
+        for id in 0..MAX_PLAYERS {
+            for axis in &[
+                AxisBinding::Vertical(id),
+                AxisBinding::Horizontal(id),
+                AxisBinding::AimVertical(id),
+                AxisBinding::AimHorizontal(id),
+            ] {
+                assert!(bindings.axis(axis).is_some(), "{} isn't bound", axis);
+                let stick = axis.stick().unwrap();
+                assert!(bindings.axis(&stick).is_some(), "{} isn't bound", stick);
+            }
+        }
+    }
+}
@@ -4,9 +4,13 @@
 
 #[macro_use]
 mod macros;
+mod campaign;
 mod components;
+mod config;
+mod difficulty;
 mod display;
 mod editor;
+mod highscores;
 mod input;
 mod states;
 mod systems;
@@ -16,10 +20,11 @@ use amethyst::{
     input::InputBundle,
     prelude::*,
     renderer::{plugins::RenderToWindow, types::DefaultBackend, RenderingBundle},
+    ui::{RenderUi, UiBundle},
     LogLevelFilter, LoggerConfig,
 };
 use display::shape_drawer::RenderCircles;
-use input::PsychoBindingTypes;
+use input::{load_bindings, PsychoBindingTypes};
 use states::MainMenu;
 use systems::MovingSystem;
 use utils::fs::root;
@@ -38,15 +43,13 @@ fn main() -> amethyst::Result<()> {
                         .expect("Failed to read display config")
                         .with_clear([0.0, 0.0, 0.0, 1.0]),
                 )
-                .with_plugin(RenderCircles),
-        )?
-        .with_bundle(
-            InputBundle::<PsychoBindingTypes>::new()
-                .with_bindings_from_file(app_root.join("config/bindings.ron"))
-                .expect("Failed to read bindings"),
+                .with_plugin(RenderCircles)
+                .with_plugin(RenderUi::default()),
         )?
+        .with_bundle(InputBundle::<PsychoBindingTypes>::new().with_bindings(load_bindings()))?
+        .with_bundle(UiBundle::<PsychoBindingTypes>::new())?
         .with(MovingSystem, "moving", &[]);
-    let mut game = Application::new(app_root.join("assets"), MainMenu, game_data)?;
+    let mut game = Application::new(app_root.join("assets"), MainMenu::default(), game_data)?;
     game.run();
     Ok(())
 }
@@ -0,0 +1,214 @@
+//! Level select screen, showing the high scores of each level
+
+use amethyst::{
+    ecs::Entity,
+    prelude::*,
+    ui::UiText,
+    winit::{ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent},
+};
+use log::error;
+use std::fs;
+
+use crate::{
+    campaign::{Campaign, SaveFile},
+    difficulty::Difficulty,
+    display::text::{create_text, default_font},
+    highscores::{Entry, HighScores, TABLE_SIZE},
+    states::{MainMenu, PracticeSetup, Quickplay, Run},
+    utils::fs::root,
+};
+
+/// Levels shown on screen at once
+const VISIBLE_LEVELS: usize = 8;
+
+/// Names of every level in the levels directory, sorted
+fn level_names() -> Vec<String> {
+    let mut names: Vec<String> = match fs::read_dir(root().join("levels")) {
+        Ok(dir) => dir
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension()? == "lua" {
+                    Some(path.file_stem()?.to_str()?.to_owned())
+                } else {
+                    None
+                }
+            })
+            .collect(),
+        Err(e) => {
+            error!("Failed to list levels: {}", e);
+            Vec::new()
+        }
+    };
+    names.sort();
+    names
+}
+
+pub struct LevelSelect {
+    players: usize,
+    difficulty: Difficulty,
+    levels: Vec<String>,
+    selected: usize,
+    high_scores: HighScores,
+    campaign: Campaign,
+    save: SaveFile,
+    texts: Vec<Entity>,
+}
+
+impl LevelSelect {
+    pub fn new(players: usize) -> Self {
+        Self {
+            players,
+            difficulty: Difficulty::default(),
+            levels: Vec::new(),
+            selected: 0,
+            high_scores: HighScores::default(),
+            campaign: Campaign::default(),
+            save: SaveFile::default(),
+            texts: Vec::new(),
+        }
+    }
+
+    /// Levels of the campaign are locked until the previous one is finished
+    fn is_locked(&self, level: &str) -> bool {
+        self.campaign
+            .position(level)
+            .map_or(false, |index| !self.save.is_unlocked(&self.campaign, index))
+    }
+
+    /// What to play for a level, which counts as campaign progress if it's part of it
+    fn run(&self, level: &str) -> Run {
+        self.campaign
+            .position(level)
+            .and_then(|index| Run::campaign(&self.campaign, index, self.players, self.difficulty))
+            .unwrap_or_else(|| Run::new(level.to_owned(), self.players, self.difficulty))
+    }
+
+    /// Updates every line of text on the screen
+    fn refresh(&self, world: &mut World) {
+        let mut lines = vec![format!(
+            "Select level ({} players, {})",
+            self.players,
+            self.difficulty.name()
+        )];
+        // Scroll so the selected level is always visible
+        let first = (self.selected + 1).saturating_sub(VISIBLE_LEVELS);
+        for i in first..first + VISIBLE_LEVELS {
+            lines.push(match self.levels.get(i) {
+                Some(level) => {
+                    let cursor = if i == self.selected { "> " } else { "" };
+                    let locked = if self.is_locked(level) {
+                        " (locked)"
+                    } else {
+                        ""
+                    };
+                    format!("{}{}{}", cursor, level, locked)
+                }
+                None => String::new(),
+            });
+        }
+        lines.push(
+            "[Up/Down] Select  [Left/Right] Difficulty  [Enter] Play  [P] Practice  [Esc] Back"
+                .to_owned(),
+        );
+        lines.push("High scores".to_owned());
+        let table: &[Entry] = match self.levels.get(self.selected) {
+            Some(level) => self.high_scores.table(level, self.difficulty),
+            None => &[],
+        };
+        for i in 0..TABLE_SIZE {
+            lines.push(match table.get(i) {
+                Some(entry) => entry.describe(i),
+                None if i == 0 => "No scores yet".to_owned(),
+                None => String::new(),
+            });
+        }
+        let mut ui_texts = world.write_storage::<UiText>();
+        for (entity, line) in self.texts.iter().zip(lines) {
+            if let Some(ui_text) = ui_texts.get_mut(*entity) {
+                ui_text.text = line;
+            }
+        }
+    }
+}
+
+impl SimpleState for LevelSelect {
+    fn on_start(&mut self, data: StateData<GameData>) {
+        let world = data.world;
+        self.difficulty = *world.read_resource::<Difficulty>();
+        self.levels = level_names();
+        self.high_scores = HighScores::load();
+        self.campaign = Campaign::load();
+        self.save = SaveFile::load();
+        let font = default_font(world);
+        let white = [1., 1., 1., 1.];
+        self.texts = vec![create_text(world, &font, String::new(), 420., 50., white)];
+        for i in 0..VISIBLE_LEVELS {
+            let y = 340. - 36. * i as f32;
+            self.texts
+                .push(create_text(world, &font, String::new(), y, 26., white));
+        }
+        self.texts
+            .push(create_text(world, &font, String::new(), 30., 22., white));
+        self.texts
+            .push(create_text(world, &font, String::new(), -40., 25., white));
+        for i in 0..TABLE_SIZE {
+            let y = -80. - 26. * i as f32;
+            self.texts
+                .push(create_text(world, &font, String::new(), y, 20., white));
+        }
+        self.refresh(world);
+    }
+
+    fn on_stop(&mut self, data: StateData<GameData>) {
+        data.world
+            .delete_entities(&self.texts)
+            .expect("Failed to delete level select");
+        self.texts.clear();
+    }
+
+    fn handle_event(&mut self, data: StateData<GameData>, event: StateEvent) -> SimpleTrans {
+        let key = match event {
+            StateEvent::Window(Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(key),
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            }) => key,
+            _ => return Trans::None,
+        };
+        let n = self.levels.len().max(1);
+        match key {
+            VirtualKeyCode::Up => self.selected = (self.selected + n - 1) % n,
+            VirtualKeyCode::Down => self.selected = (self.selected + 1) % n,
+            VirtualKeyCode::Left | VirtualKeyCode::Right => {
+                let offset = if key == VirtualKeyCode::Left { -1 } else { 1 };
+                self.difficulty = self.difficulty.cycle(offset);
+                // Remember it for the next time
+                *data.world.write_resource::<Difficulty>() = self.difficulty;
+            }
+            VirtualKeyCode::Return => match self.levels.get(self.selected) {
+                Some(level) if !self.is_locked(level) => {
+                    return Trans::Switch(Box::new(Quickplay::new(self.run(level))));
+                }
+                _ => {}
+            },
+            VirtualKeyCode::P => match self.levels.get(self.selected) {
+                Some(level) if !self.is_locked(level) => {
+                    return Trans::Switch(Box::new(PracticeSetup::new(self.run(level))));
+                }
+                _ => {}
+            },
+            VirtualKeyCode::Escape => return Trans::Switch(Box::new(MainMenu::default())),
+            _ => return Trans::None,
+        }
+        self.refresh(data.world);
+        Trans::None
+    }
+}
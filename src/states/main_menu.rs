@@ -1,37 +1,181 @@
 //! Main menu state
 
-use amethyst::prelude::*;
-use amethyst::winit::{ElementState, Event, MouseButton, WindowEvent};
+use amethyst::{
+    ecs::Entity,
+    prelude::*,
+    ui::UiText,
+    winit::{ElementState, Event, KeyboardInput, MouseButton, VirtualKeyCode, WindowEvent},
+};
 use log::*;
 
-use crate::states::Quickplay;
+use crate::{
+    campaign::{Campaign, SaveFile},
+    difficulty::Difficulty,
+    display::text::{create_text, default_font},
+    states::{LevelSelect, Options, Quickplay, Run},
+};
 
 #[derive(Default)]
-pub struct MainMenu;
+pub struct MainMenu {
+    /// Where the saved campaign continues from, if there's a save
+    continue_run: Option<Run>,
+    /// Set after pressing [N] with a save, which has to be pressed again
+    confirm_new_game: bool,
+    new_game_text: Option<Entity>,
+    difficulty_text: Option<Entity>,
+    texts: Vec<Entity>,
+}
+
+fn difficulty_line(difficulty: Difficulty) -> String {
+    format!("[D] Difficulty: {}", difficulty.name())
+}
+
+/// Starts the campaign over, forgetting the saved progress
+fn new_game(difficulty: Difficulty) -> SimpleTrans {
+    let save = SaveFile::default();
+    if let Err(e) = save.save() {
+        error!("Failed to save progress: {}", e);
+    }
+    match Run::campaign(&Campaign::load(), 0, 1, difficulty) {
+        Some(run) => Trans::Switch(Box::new(Quickplay::new(run))),
+        None => {
+            error!("The campaign has no levels");
+            Trans::None
+        }
+    }
+}
+
+impl MainMenu {
+    fn set_confirm_new_game(&mut self, world: &mut World, confirm: bool) {
+        self.confirm_new_game = confirm;
+        let mut ui_texts = world.write_storage::<UiText>();
+        if let Some(ui_text) = self.new_game_text.and_then(|e| ui_texts.get_mut(e)) {
+            ui_text.text = if confirm {
+                "[N] Press again to erase your progress".to_owned()
+            } else {
+                "[N] New game".to_owned()
+            };
+        }
+    }
+}
 
 impl SimpleState for MainMenu {
-    fn on_start(&mut self, _data: StateData<'_, GameData<'_, '_>>) {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
         info!("Entered main menu");
+        let world = data.world;
+        // The chosen difficulty is kept as a resource while the game is open
+        let difficulty = *world
+            .entry::<Difficulty>()
+            .or_insert_with(Difficulty::default);
+        if SaveFile::exists() {
+            let campaign = Campaign::load();
+            // Replay the last level once the whole campaign is finished
+            let index = SaveFile::load().continue_from(&campaign);
+            self.continue_run = Run::campaign(&campaign, index, 1, difficulty);
+        }
+        let font = default_font(world);
+        let white = [1., 1., 1., 1.];
+        let mut lines = Vec::new();
+        if let Some(run) = &self.continue_run {
+            lines.push(format!("[Enter] Continue: {}", run.level_name));
+        }
+        lines.push("[N] New game".to_owned());
+        lines.push("[Click] Level select".to_owned());
+        lines.push("[2] Two players".to_owned());
+        lines.push("[O] Options".to_owned());
+        lines.push(difficulty_line(difficulty));
+        self.texts = vec![create_text(
+            world,
+            &font,
+            "PsyChO: The Ball".to_owned(),
+            100.,
+            60.,
+            white,
+        )];
+        for (i, line) in lines.into_iter().enumerate() {
+            let y = -40. - 40. * i as f32;
+            self.texts
+                .push(create_text(world, &font, line, y, 25., white));
+        }
+        // After the title and the continue line
+        self.new_game_text = self
+            .texts
+            .get(1 + self.continue_run.is_some() as usize)
+            .copied();
+        self.difficulty_text = self.texts.last().copied();
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        data.world
+            .delete_entities(&self.texts)
+            .expect("Failed to delete main menu");
+        self.texts.clear();
     }
 
     fn handle_event(
         &mut self,
-        _data: StateData<'_, GameData<'_, '_>>,
+        data: StateData<'_, GameData<'_, '_>>,
         event: StateEvent,
     ) -> SimpleTrans {
-        if let StateEvent::Window(Event::WindowEvent {
-            event:
-                WindowEvent::MouseInput {
-                    state: ElementState::Pressed,
-                    button: MouseButton::Left,
-                    ..
-                },
-            ..
-        }) = event
-        {
-            Trans::Switch(Box::new(Quickplay::new("level1".to_owned())))
-        } else {
-            Trans::None
+        match event {
+            StateEvent::Window(Event::WindowEvent {
+                event:
+                    WindowEvent::MouseInput {
+                        state: ElementState::Pressed,
+                        button: MouseButton::Left,
+                        ..
+                    },
+                ..
+            }) => Trans::Switch(Box::new(LevelSelect::new(1))),
+            StateEvent::Window(Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(key),
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            }) => {
+                // Any other key cancels starting a new game
+                let confirming = self.confirm_new_game;
+                if confirming {
+                    self.set_confirm_new_game(data.world, false);
+                }
+                match key {
+                    VirtualKeyCode::Key2 => Trans::Switch(Box::new(LevelSelect::new(2))),
+                    VirtualKeyCode::Return => match &self.continue_run {
+                        Some(run) => Trans::Switch(Box::new(Quickplay::new(run.clone()))),
+                        None => Trans::None,
+                    },
+                    // Ask first when it would erase the saved progress
+                    VirtualKeyCode::N if self.continue_run.is_some() && !confirming => {
+                        self.set_confirm_new_game(data.world, true);
+                        Trans::None
+                    }
+                    VirtualKeyCode::N => new_game(*data.world.read_resource::<Difficulty>()),
+                    VirtualKeyCode::D => {
+                        let mut difficulty = data.world.write_resource::<Difficulty>();
+                        *difficulty = difficulty.cycle(1);
+                        if let Some(run) = &mut self.continue_run {
+                            run.difficulty = *difficulty;
+                        }
+                        let mut ui_texts = data.world.write_storage::<UiText>();
+                        if let Some(ui_text) =
+                            self.difficulty_text.and_then(|e| ui_texts.get_mut(e))
+                        {
+                            ui_text.text = difficulty_line(*difficulty);
+                        }
+                        Trans::None
+                    }
+                    VirtualKeyCode::O => Trans::Switch(Box::new(Options::default())),
+                    _ => Trans::None,
+                }
+            }
+            _ => Trans::None,
         }
     }
 }
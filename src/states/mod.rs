@@ -1,5 +1,13 @@
+mod level_select;
 mod main_menu;
+mod options;
+mod practice;
 mod quickplay;
+mod results;
 
+pub use level_select::LevelSelect;
 pub use main_menu::MainMenu;
-pub use quickplay::Quickplay;
+pub use options::Options;
+pub use practice::PracticeSetup;
+pub use quickplay::{Quickplay, Run};
+pub use results::{GameOver, LevelComplete};
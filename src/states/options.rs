@@ -0,0 +1,362 @@
+//! Options screen, where every control can be rebound
+
+use amethyst::{
+    config::Config,
+    ecs::Entity,
+    input::{Axis, Bindings, Button, InputHandler},
+    prelude::*,
+    ui::UiText,
+    winit::{ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent},
+};
+use failure::Error;
+use log::{error, info};
+use ron::ser::{to_string_pretty, PrettyConfig};
+use std::{fs, io};
+
+use crate::{
+    components::MAX_PLAYERS,
+    display::text::{create_text, default_font},
+    input::{ActionBinding, AxisBinding, PsychoBindingTypes, BINDINGS_FILE},
+    states::MainMenu,
+    utils::fs::{root, user_config_file},
+};
+
+/// Something that can be bound to a button
+#[derive(Debug, Clone)]
+enum Control {
+    /// The positive (`true`) or negative side of an axis
+    Axis(AxisBinding, bool),
+    Action(ActionBinding),
+}
+
+impl Control {
+    /// Every control of player `id`
+    fn all(id: usize) -> Vec<Control> {
+        use AxisBinding::*;
+        let mut controls = Vec::new();
+        for axis in [
+            Vertical(id),
+            Horizontal(id),
+            AimVertical(id),
+            AimHorizontal(id),
+        ]
+        .iter()
+        {
+            controls.push(Control::Axis(axis.clone(), true));
+            controls.push(Control::Axis(axis.clone(), false));
+        }
+        controls.extend(
+            [
+                ActionBinding::Shoot(id),
+                ActionBinding::NextWeapon(id),
+                ActionBinding::PreviousWeapon(id),
+                ActionBinding::Bomb(id),
+                ActionBinding::Dash(id),
+                ActionBinding::Psycho(id),
+            ]
+            .iter()
+            .cloned()
+            .map(Control::Action),
+        );
+        controls
+    }
+
+    fn name(&self) -> &'static str {
+        use AxisBinding::*;
+        match self {
+            Control::Axis(axis, positive) => match (axis, positive) {
+                (Vertical(_), true) => "Move up",
+                (Vertical(_), false) => "Move down",
+                (Horizontal(_), true) => "Move right",
+                (Horizontal(_), false) => "Move left",
+                (AimVertical(_), true) => "Aim up",
+                (AimVertical(_), false) => "Aim down",
+                (AimHorizontal(_), true) => "Aim right",
+                (AimHorizontal(_), false) => "Aim left",
+                // Gamepad sticks can't be rebound, so they aren't listed
+                _ => "Gamepad stick",
+            },
+            Control::Action(action) => match action {
+                ActionBinding::Shoot(_) => "Shoot",
+                ActionBinding::NextWeapon(_) => "Next weapon",
+                ActionBinding::PreviousWeapon(_) => "Previous weapon",
+                ActionBinding::Bomb(_) => "Bomb",
+                ActionBinding::Dash(_) => "Dash",
+                ActionBinding::Psycho(_) => "Psycho",
+            },
+        }
+    }
+
+    /// Main button bound to this control
+    fn describe(&self, bindings: &Bindings<PsychoBindingTypes>) -> String {
+        let described = match self {
+            Control::Axis(id, positive) => {
+                let mut axis = bindings.axis(id).cloned();
+                axis.as_mut()
+                    .and_then(emulated)
+                    .map(|(pos, neg)| format!("{:?}", if *positive { pos } else { neg }))
+            }
+            Control::Action(id) => bindings.action_bindings(id).next().map(|combo| {
+                combo
+                    .iter()
+                    .map(|button| format!("{:?}", button))
+                    .collect::<Vec<_>>()
+                    .join(" + ")
+            }),
+        };
+        described.unwrap_or_else(|| "(none)".to_owned())
+    }
+
+    /// Replaces the main button of this control. Other bindings, like gamepad
+    /// ones, are kept. Bindings are left untouched if the button is in use.
+    fn rebind(
+        &self,
+        bindings: &mut Bindings<PsychoBindingTypes>,
+        button: Button,
+    ) -> Result<(), String> {
+        match self {
+            Control::Axis(id, positive) => {
+                let old = bindings
+                    .axis(id)
+                    .cloned()
+                    .ok_or_else(|| "Axis isn't bound".to_owned())?;
+                let mut new = old.clone();
+                let (pos, neg) = emulated(&mut new)
+                    .ok_or_else(|| "No keyboard binding to replace".to_owned())?;
+                *(if *positive { pos } else { neg }) = button;
+                bindings.remove_axis(id);
+                if let Err(e) = bindings.insert_axis(id.clone(), new) {
+                    bindings
+                        .insert_axis(id.clone(), old)
+                        .expect("Failed to restore axis");
+                    return Err(e.to_string());
+                }
+            }
+            Control::Action(id) => {
+                let old = bindings
+                    .action_bindings(id)
+                    .map(|combo| combo.to_vec())
+                    .collect::<Vec<_>>();
+                let mut new = vec![vec![button]];
+                new.extend(old.iter().skip(1).cloned());
+                for combo in &old {
+                    bindings.remove_action_binding(id, combo);
+                }
+                for combo in &new {
+                    if let Err(e) = bindings.insert_action_binding(id.clone(), combo.clone()) {
+                        for combo in &new {
+                            bindings.remove_action_binding(id, combo);
+                        }
+                        for combo in old {
+                            bindings
+                                .insert_action_binding(id.clone(), combo)
+                                .expect("Failed to restore action");
+                        }
+                        return Err(e.to_string());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The buttons of a keyboard axis, as (positive, negative)
+fn emulated(axis: &mut Axis) -> Option<(&mut Button, &mut Button)> {
+    match axis {
+        Axis::Emulated { pos, neg } => Some((pos, neg)),
+        _ => None,
+    }
+}
+
+/// Saves the bindings to the user's copy of the config file
+fn save(bindings: &Bindings<PsychoBindingTypes>) -> Result<(), Error> {
+    let path = user_config_file(BINDINGS_FILE);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, to_string_pretty(bindings, PrettyConfig::default())?)?;
+    info!("Saved bindings to {:?}", path);
+    Ok(())
+}
+
+/// Goes back to the shipped bindings, removing the user's copy
+fn reset() -> Result<Bindings<PsychoBindingTypes>, Error> {
+    let bindings = Bindings::load(root().join("config").join(BINDINGS_FILE))?;
+    if let Err(e) = fs::remove_file(user_config_file(BINDINGS_FILE)) {
+        if e.kind() != io::ErrorKind::NotFound {
+            return Err(e.into());
+        }
+    }
+    Ok(bindings)
+}
+
+#[derive(Default)]
+pub struct Options {
+    /// Player whose controls are shown
+    player: usize,
+    controls: Vec<Control>,
+    selected: usize,
+    /// Set while waiting for a button to bind. It's false until every button
+    /// is released, so the key that started the rebinding isn't used.
+    waiting: Option<bool>,
+    status: String,
+    texts: Vec<Entity>,
+}
+
+impl Options {
+    /// Updates every line of text on the screen
+    fn refresh(&self, world: &mut World) {
+        let input = world.read_resource::<InputHandler<PsychoBindingTypes>>();
+        let mut lines = vec![format!("< Player {} >", self.player + 1)];
+        for (i, control) in self.controls.iter().enumerate() {
+            let cursor = if i == self.selected { "> " } else { "" };
+            lines.push(format!(
+                "{}{}: {}",
+                cursor,
+                control.name(),
+                control.describe(&input.bindings)
+            ));
+        }
+        lines.push(
+            "[Left/Right] Player  [Enter] Rebind  [Backspace] Reset all  [Esc] Back".to_owned(),
+        );
+        lines.push(self.status.clone());
+        let mut ui_texts = world.write_storage::<UiText>();
+        for (entity, line) in self.texts.iter().zip(lines) {
+            if let Some(ui_text) = ui_texts.get_mut(*entity) {
+                ui_text.text = line;
+            }
+        }
+    }
+
+    fn rebind(&mut self, world: &mut World, button: Button) {
+        let control = &self.controls[self.selected];
+        let mut input = world.write_resource::<InputHandler<PsychoBindingTypes>>();
+        self.status = match control.rebind(&mut input.bindings, button) {
+            Ok(()) => match save(&input.bindings) {
+                Ok(()) => format!("{} rebound", control.name()),
+                Err(e) => {
+                    error!("Failed to save bindings: {}", e);
+                    format!("Failed to save: {}", e)
+                }
+            },
+            Err(e) => e,
+        };
+    }
+}
+
+impl SimpleState for Options {
+    fn on_start(&mut self, data: StateData<GameData>) {
+        let world = data.world;
+        self.controls = Control::all(self.player);
+        let font = default_font(world);
+        let white = [1., 1., 1., 1.];
+        self.texts = vec![create_text(world, &font, String::new(), 420., 50., white)];
+        for i in 0..self.controls.len() {
+            let y = 340. - 36. * i as f32;
+            self.texts
+                .push(create_text(world, &font, String::new(), y, 24., white));
+        }
+        for &y in &[-340., -390.] {
+            self.texts
+                .push(create_text(world, &font, String::new(), y, 22., white));
+        }
+        self.refresh(world);
+    }
+
+    fn on_stop(&mut self, data: StateData<GameData>) {
+        data.world
+            .delete_entities(&self.texts)
+            .expect("Failed to delete options screen");
+        self.texts.clear();
+    }
+
+    fn handle_event(&mut self, data: StateData<GameData>, event: StateEvent) -> SimpleTrans {
+        let key = match event {
+            StateEvent::Window(Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(key),
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            }) => key,
+            _ => return Trans::None,
+        };
+        let n = self.controls.len();
+        if self.waiting.is_some() {
+            // Escape can't be bound, it always cancels
+            if key == VirtualKeyCode::Escape {
+                self.waiting = None;
+                self.status = String::new();
+            }
+        } else {
+            match key {
+                VirtualKeyCode::Up => self.selected = (self.selected + n - 1) % n,
+                VirtualKeyCode::Down => self.selected = (self.selected + 1) % n,
+                VirtualKeyCode::Left | VirtualKeyCode::Right => {
+                    let step = if key == VirtualKeyCode::Left {
+                        MAX_PLAYERS - 1
+                    } else {
+                        1
+                    };
+                    self.player = (self.player + step) % MAX_PLAYERS;
+                    self.controls = Control::all(self.player);
+                }
+                VirtualKeyCode::Return => {
+                    self.waiting = Some(false);
+                    self.status = format!(
+                        "Press a button for {} ([Esc] to cancel)",
+                        self.controls[self.selected].name()
+                    );
+                }
+                VirtualKeyCode::Back => {
+                    self.status = match reset() {
+                        Ok(bindings) => {
+                            data.world
+                                .write_resource::<InputHandler<PsychoBindingTypes>>()
+                                .bindings = bindings;
+                            "Controls reset".to_owned()
+                        }
+                        Err(e) => format!("Failed to reset: {}", e),
+                    }
+                }
+                VirtualKeyCode::Escape => return Trans::Switch(Box::new(MainMenu::default())),
+                _ => return Trans::None,
+            }
+        }
+        self.refresh(data.world);
+        Trans::None
+    }
+
+    fn update(&mut self, data: &mut StateData<GameData>) -> SimpleTrans {
+        if let Some(released) = self.waiting {
+            let pressed = data
+                .world
+                .read_resource::<InputHandler<PsychoBindingTypes>>()
+                .buttons_that_are_down()
+                .find(|button| {
+                    !matches!(
+                        button,
+                        Button::ScanCode(_) | Button::Key(VirtualKeyCode::Escape)
+                    )
+                });
+            match (released, pressed) {
+                (false, None) => self.waiting = Some(true),
+                (true, Some(button)) => {
+                    self.waiting = None;
+                    self.rebind(data.world, button);
+                    self.refresh(data.world);
+                }
+                _ => {}
+            }
+        }
+        Trans::None
+    }
+}
@@ -0,0 +1,225 @@
+//! Practice mode setup, choosing where to start a level and how to play it
+
+use amethyst::{
+    ecs::Entity,
+    prelude::*,
+    ui::UiText,
+    winit::{ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent},
+};
+use log::error;
+
+use crate::{
+    display::text::{create_text, default_font},
+    editor::reader::{lua::LuaLevel, LevelEvent},
+    states::{LevelSelect, Quickplay, Run},
+    systems::gameplay::practice::{Practice, PRACTICE_SPEEDS},
+    utils::fs::root,
+};
+
+/// A place practice can start from
+struct Checkpoint {
+    name: String,
+    /// Events before it
+    index: usize,
+}
+
+/// Checkpoints of a level, found by running through its script without
+/// playing it, and how many events it has
+fn read_checkpoints(run: &Run) -> (Vec<Checkpoint>, usize) {
+    let mut checkpoints = vec![Checkpoint {
+        name: "Beginning".to_owned(),
+        index: 0,
+    }];
+    let path = root().join(format!("levels/{}.lua", run.level_name));
+    let mut level = match LuaLevel::new(&path) {
+        Ok(level) => level,
+        Err(e) => {
+            error!("Failed to load level {}: {}", run.level_name, e);
+            return (checkpoints, 0);
+        }
+    };
+    level.set_difficulty(run.difficulty);
+    let mut events = 0;
+    for (index, event) in level.enumerate() {
+        if let LevelEvent::Checkpoint(name) = event {
+            checkpoints.push(Checkpoint { name, index });
+        }
+        events = index + 1;
+    }
+    (checkpoints, events)
+}
+
+/// Settings that can be changed, one per line
+#[derive(Clone, Copy, PartialEq)]
+enum Row {
+    Checkpoint,
+    Event,
+    Loop,
+    Invincible,
+    Speed,
+}
+
+const ROWS: [Row; 5] = [
+    Row::Checkpoint,
+    Row::Event,
+    Row::Loop,
+    Row::Invincible,
+    Row::Speed,
+];
+
+pub struct PracticeSetup {
+    run: Run,
+    practice: Practice,
+    checkpoints: Vec<Checkpoint>,
+    events: usize,
+    selected: usize,
+    texts: Vec<Entity>,
+}
+
+impl PracticeSetup {
+    pub fn new(run: Run) -> Self {
+        let practice = run.practice.clone().unwrap_or_default();
+        Self {
+            practice,
+            run,
+            checkpoints: Vec::new(),
+            events: 0,
+            selected: 0,
+            texts: Vec::new(),
+        }
+    }
+
+    /// Position in `checkpoints` of the last one at or before the start
+    fn checkpoint(&self) -> usize {
+        self.checkpoints
+            .iter()
+            .rposition(|c| c.index <= self.practice.start)
+            .unwrap_or(0)
+    }
+
+    fn change(&mut self, row: Row, offset: isize) {
+        match row {
+            Row::Checkpoint => {
+                let current = self.checkpoint();
+                let target = if offset > 0 {
+                    (current + 1).min(self.checkpoints.len() - 1)
+                } else if self.practice.start > self.checkpoints[current].index {
+                    current
+                } else {
+                    current.saturating_sub(1)
+                };
+                self.practice.start = self.checkpoints[target].index;
+            }
+            Row::Event => {
+                let last = self.events.saturating_sub(1) as isize;
+                self.practice.start =
+                    (self.practice.start as isize + offset).max(0).min(last) as usize;
+            }
+            Row::Loop => self.practice.loop_section = !self.practice.loop_section,
+            Row::Invincible => self.practice.invincible = !self.practice.invincible,
+            Row::Speed => {
+                let n = PRACTICE_SPEEDS.len() as isize;
+                let i = PRACTICE_SPEEDS
+                    .iter()
+                    .position(|&speed| speed == self.practice.speed)
+                    .unwrap_or(0) as isize;
+                self.practice.speed = PRACTICE_SPEEDS[(i + offset).rem_euclid(n) as usize];
+            }
+        }
+    }
+
+    fn describe(&self, row: Row) -> String {
+        match row {
+            Row::Checkpoint => format!("Checkpoint: {}", self.checkpoints[self.checkpoint()].name),
+            Row::Event => format!("Event: {} / {}", self.practice.start, self.events),
+            Row::Loop if self.practice.loop_section => "Loop: until the next checkpoint".to_owned(),
+            Row::Loop => "Loop: until the end of the level".to_owned(),
+            Row::Invincible if self.practice.invincible => "Invincible: yes".to_owned(),
+            Row::Invincible => "Invincible: no".to_owned(),
+            Row::Speed => format!("Speed: {}x", self.practice.speed),
+        }
+    }
+
+    /// Updates every line of text on the screen
+    fn refresh(&self, world: &mut World) {
+        let mut lines = vec![format!("Practice: {}", self.run.level_name)];
+        for (i, &row) in ROWS.iter().enumerate() {
+            let cursor = if i == self.selected { "> " } else { "" };
+            lines.push(format!("{}{}", cursor, self.describe(row)));
+        }
+        lines.push("[Up/Down] Select  [Left/Right] Change  [Enter] Start  [Esc] Back".to_owned());
+        let mut ui_texts = world.write_storage::<UiText>();
+        for (entity, line) in self.texts.iter().zip(lines) {
+            if let Some(ui_text) = ui_texts.get_mut(*entity) {
+                ui_text.text = line;
+            }
+        }
+    }
+
+    fn start(&self) -> SimpleTrans {
+        Trans::Switch(Box::new(Quickplay::new(Run {
+            practice: Some(self.practice.clone()),
+            ..self.run.clone()
+        })))
+    }
+}
+
+impl SimpleState for PracticeSetup {
+    fn on_start(&mut self, data: StateData<GameData>) {
+        let world = data.world;
+        let (checkpoints, events) = read_checkpoints(&self.run);
+        self.checkpoints = checkpoints;
+        self.events = events;
+        let font = default_font(world);
+        let white = [1., 1., 1., 1.];
+        self.texts = vec![create_text(world, &font, String::new(), 300., 50., white)];
+        for i in 0..ROWS.len() {
+            let y = 200. - 40. * i as f32;
+            self.texts
+                .push(create_text(world, &font, String::new(), y, 26., white));
+        }
+        self.texts
+            .push(create_text(world, &font, String::new(), -100., 22., white));
+        self.refresh(world);
+    }
+
+    fn on_stop(&mut self, data: StateData<GameData>) {
+        data.world
+            .delete_entities(&self.texts)
+            .expect("Failed to delete practice setup");
+        self.texts.clear();
+    }
+
+    fn handle_event(&mut self, data: StateData<GameData>, event: StateEvent) -> SimpleTrans {
+        let key = match event {
+            StateEvent::Window(Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(key),
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            }) => key,
+            _ => return Trans::None,
+        };
+        let n = ROWS.len();
+        match key {
+            VirtualKeyCode::Up => self.selected = (self.selected + n - 1) % n,
+            VirtualKeyCode::Down => self.selected = (self.selected + 1) % n,
+            VirtualKeyCode::Left => self.change(ROWS[self.selected], -1),
+            VirtualKeyCode::Right => self.change(ROWS[self.selected], 1),
+            VirtualKeyCode::Return => return self.start(),
+            VirtualKeyCode::Escape => {
+                return Trans::Switch(Box::new(LevelSelect::new(self.run.players)))
+            }
+            _ => return Trans::None,
+        }
+        self.refresh(data.world);
+        Trans::None
+    }
+}
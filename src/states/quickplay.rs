@@ -1,95 +1,227 @@
 //! Quickplay state
 
 use amethyst::{
-    core::ArcThreadPool,
-    ecs::{Dispatcher, DispatcherBuilder, Entities, Entity, Join, ReadStorage},
+    config::Config,
+    core::math::Point2,
+    core::{timing::Time, ArcThreadPool},
+    ecs::{Dispatcher, DispatcherBuilder, Entities, Entity, Join, LazyUpdate, ReadStorage},
     prelude::*,
+    ui::UiText,
     winit::{ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent},
 };
-use log::info;
+use log::{error, info};
 
 use crate::{
-    components::{Circle, Color, EnemySpawner, Player, Transform, Triangle},
+    campaign::{Campaign, SaveFile},
+    components::{Arsenal, Circle, Color, EnemySpawner, Player, Rectangle, Transform, Triangle},
+    config::GameplayConfig,
+    difficulty::Difficulty,
     display::{HEIGHT as H, WIDTH as W},
-    editor::executor::LevelExecutorSystem,
-    states::MainMenu,
+    editor::executor::{LevelExecutorSystem, LevelProgress},
+    states::{GameOver, LevelComplete, MainMenu, PracticeSetup},
     systems::{
-        gameplay::{CollisionSystem, EnemySpawnerSystem, LeaveScreenSystem},
+        gameplay::{
+            play_area::PlayArea, practice::Practice, psycho::PsychoMeter, score::Score,
+            slow_motion::SlowMotion, stats::Stats, BossSystem, CollisionSystem, EnemySpawnerSystem,
+            LeaveScreenSystem, OrbitSystem, PlayAreaSystem, PsychoSystem, SlowMotionSystem,
+        },
         particles::FadeSystem,
-        player::{CollisionSystem as PlayerCollisionSystem, MoveSystem, ShootSystem},
+        player::{
+            bomb::Bombs,
+            respawn::{spawn_point, Lives, PlayerCount},
+            BombSystem, CollisionSystem as PlayerCollisionSystem, GrazeSystem, MoveSystem,
+            RespawnSystem, ShootSystem,
+        },
+        HudSystem,
     },
+    utils::{creator::LazyCreator, fs::root, random::GameRng},
 };
 
+/// Real seconds between losing the last life and the game over screen
+const GAME_OVER_DELAY: f64 = 1.5;
+
+/// What is being played, kept when restarting
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub level_name: String,
+    pub players: usize,
+    pub difficulty: Difficulty,
+    /// Index of the level in the campaign, when playing through it
+    pub campaign_level: Option<usize>,
+    pub practice: Option<Practice>,
+}
+
+impl Run {
+    pub fn new(level_name: String, players: usize, difficulty: Difficulty) -> Self {
+        Self {
+            level_name,
+            players,
+            difficulty,
+            campaign_level: None,
+            practice: None,
+        }
+    }
+
+    /// A level of the campaign, if it has that many levels
+    pub fn campaign(
+        campaign: &Campaign,
+        index: usize,
+        players: usize,
+        difficulty: Difficulty,
+    ) -> Option<Self> {
+        campaign.levels.get(index).map(|level_name| Self {
+            level_name: level_name.clone(),
+            players,
+            difficulty,
+            campaign_level: Some(index),
+            practice: None,
+        })
+    }
+
+    /// The next level of the campaign, if there's one
+    pub fn next(&self) -> Option<Self> {
+        let index = self.campaign_level?;
+        Self::campaign(&Campaign::load(), index + 1, self.players, self.difficulty)
+    }
+}
+
 pub struct Quickplay<'a, 'b> {
-    level_name: String,
+    run: Run,
     dispatcher: Option<Dispatcher<'a, 'b>>,
+    game_over_at: Option<f64>,
 }
 
 impl<'a, 'b> Quickplay<'a, 'b> {
-    pub fn new(level_name: String) -> Self {
+    pub fn new(run: Run) -> Self {
         Self {
-            level_name,
+            run,
             dispatcher: None,
+            game_over_at: None,
         }
     }
 }
 
 impl<'a, 'b> Quickplay<'a, 'b> {
     fn initialize_balls(&mut self, world: &mut World) {
-        world
-            .create_entity()
-            .with(Circle::with_radius(24.))
-            .with(Color::rgb(0.3, 0.4, 1.))
-            .with(Transform::new(W / 2., H / 2.))
-            .with(Player)
-            .build();
+        let (lazy, entities) = (world.read_resource::<LazyUpdate>(), world.entities());
+        let creator = LazyCreator::new(&lazy, &entities);
+        let center = Point2::new(W / 2., H / 2.);
+        let players = self.run.players;
+        for id in 0..players {
+            creator.create_player(id, spawn_point(center, id, players), None);
+        }
     }
 }
 
 impl<'a, 'b> SimpleState for Quickplay<'a, 'b> {
     fn on_start(&mut self, data: StateData<GameData>) {
-        info!("Started quickplay on level {}!", self.level_name);
+        info!("Started quickplay on level {}!", self.run.level_name);
+        let practice = self.run.practice.clone().unwrap_or_default();
         let mut dispatch = DispatcherBuilder::new()
             .with_pool((*data.world.read_resource::<ArcThreadPool>()).clone())
             .with(MoveSystem::default(), "player_move", &[])
             .with_barrier()
             .with(
-                LevelExecutorSystem::from_lua(&self.level_name),
+                LevelExecutorSystem::from_lua(&self.run.level_name, self.run.difficulty)
+                    .skipping(practice.start),
                 "level_exec",
                 &[],
             )
-            .with(LeaveScreenSystem::default(), "leave_screen", &[])
+            .with(OrbitSystem::default(), "orbit", &[])
+            .with(LeaveScreenSystem::default(), "leave_screen", &["orbit"])
             .with(CollisionSystem::default(), "collision", &["leave_screen"])
             .with(
                 PlayerCollisionSystem::default(),
                 "player_collision",
                 &["player_move"],
             )
+            .with(
+                GrazeSystem::default(),
+                "player_graze",
+                &["player_collision"],
+            )
             .with(
                 ShootSystem::default(),
                 "player_shoot",
                 &["player_collision"],
             )
+            .with(
+                RespawnSystem::default(),
+                "player_respawn",
+                &["player_collision"],
+            )
+            .with(BombSystem::default(), "player_bomb", &["collision"])
+            .with(BossSystem::default(), "boss", &["collision", "player_bomb"])
             .with(FadeSystem::default(), "particle_fade", &[])
+            .with(
+                HudSystem::default(),
+                "hud",
+                &["player_respawn", "player_bomb"],
+            )
+            .with(PlayAreaSystem::default(), "play_area", &["level_exec"])
+            .with(
+                PsychoSystem::default(),
+                "psycho",
+                &["collision", "player_graze"],
+            )
+            .with(
+                SlowMotionSystem::default(),
+                "slow_motion",
+                &["player_collision", "psycho"],
+            )
             .with(EnemySpawnerSystem::default(), "enemy_spawner", &[])
             .build();
         let world = data.world;
+        let config = GameplayConfig::load(root().join("config/gameplay.ron"))
+            .expect("Failed to read gameplay config");
+        let arsenal = Arsenal::load(root().join("config/weapons.ron"))
+            .expect("Failed to read weapons config");
+        // Guns index into the weapons, so there must be at least one
+        assert!(
+            !arsenal.weapons.is_empty(),
+            "config/weapons.ron must have at least one weapon"
+        );
+        world.insert(arsenal);
+        world.insert(Lives::new(self.run.difficulty.lives(config.lives)));
+        world.insert(self.run.difficulty);
+        world.insert(Bombs {
+            remaining: config.bombs,
+        });
+        world.insert(config);
+        world.insert(LevelProgress::default());
+        world.insert(PlayArea::default());
+        world.insert(SlowMotion::default());
+        world.insert(practice);
+        world.insert(PlayerCount(self.run.players));
+        world.insert(Score::default());
+        world.insert(PsychoMeter::default());
+        world.insert(GameRng::default());
+        let now = world.read_resource::<Time>().absolute_real_time_seconds();
+        world.insert(Stats::new(now));
         dispatch.setup(world);
         self.initialize_balls(world);
         self.dispatcher = Some(dispatch);
     }
 
     fn on_stop(&mut self, data: StateData<GameData>) {
-        // Delete all circles
-        let (entities, circles, enemy_spawners, triangles): (
+        // Don't leave the menus in slow motion
+        data.world.write_resource::<Time>().set_time_scale(1.);
+        // Delete all circles and the HUD
+        let (entities, circles, enemy_spawners, triangles, rectangles, ui_texts): (
             Entities,
             ReadStorage<'_, Circle>,
             ReadStorage<'_, EnemySpawner>,
             ReadStorage<'_, Triangle>,
+            ReadStorage<'_, Rectangle>,
+            ReadStorage<'_, UiText>,
         ) = data.world.system_data();
         for (c_id, _) in (
             &entities,
-            circles.mask() | enemy_spawners.mask() | triangles.mask(),
+            circles.mask()
+                | enemy_spawners.mask()
+                | triangles.mask()
+                | rectangles.mask()
+                | ui_texts.mask(),
         )
             .join()
         {
@@ -101,6 +233,43 @@ impl<'a, 'b> SimpleState for Quickplay<'a, 'b> {
         if let Some(dispatcher) = &mut self.dispatcher {
             dispatcher.dispatch(&data.world);
         }
+        let now = data
+            .world
+            .read_resource::<Time>()
+            .absolute_real_time_seconds();
+        if let Some(practice) = &self.run.practice {
+            // Practice loops the section instead of ending the level
+            let progress = data.world.read_resource::<LevelProgress>();
+            let section_done =
+                progress.complete || (practice.loop_section && progress.checkpoint_reached);
+            if data.world.read_resource::<Stats>().deaths > 0 {
+                self.game_over_at.get_or_insert(now + GAME_OVER_DELAY);
+            }
+            if section_done || self.game_over_at.map_or(false, |at| now >= at) {
+                return Trans::Switch(Box::new(Quickplay::new(self.run.clone())));
+            }
+            return Trans::None;
+        }
+        if data.world.read_resource::<LevelProgress>().complete {
+            data.world.write_resource::<Stats>().finish(now);
+            if self.run.campaign_level.is_some() {
+                let mut save = SaveFile::load();
+                save.complete(&self.run.level_name);
+                if let Err(e) = save.save() {
+                    error!("Failed to save progress: {}", e);
+                }
+            }
+            return Trans::Switch(Box::new(LevelComplete::new(self.run.clone())));
+        }
+        if data.world.read_resource::<Lives>().remaining == 0 {
+            if self.game_over_at.is_none() {
+                data.world.write_resource::<Stats>().finish(now);
+                self.game_over_at = Some(now + GAME_OVER_DELAY);
+            }
+            if now >= self.game_over_at.unwrap() {
+                return Trans::Switch(Box::new(GameOver::new(self.run.clone())));
+            }
+        }
         Trans::None
     }
 
@@ -119,7 +288,10 @@ impl<'a, 'b> SimpleState for Quickplay<'a, 'b> {
             ..
         }) = event
         {
-            Trans::Switch(Box::new(MainMenu::default()))
+            match &self.run.practice {
+                Some(_) => Trans::Switch(Box::new(PracticeSetup::new(self.run.clone()))),
+                None => Trans::Switch(Box::new(MainMenu::default())),
+            }
         } else {
             Trans::None
         }
@@ -0,0 +1,188 @@
+//! Screens shown when a level ends, either by losing all lives or by finishing it
+
+use amethyst::{
+    ecs::Entity,
+    prelude::*,
+    winit::{ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent},
+};
+use log::{error, info};
+
+use crate::{
+    display::text::{create_text, default_font},
+    highscores::{Entry, HighScores},
+    states::{MainMenu, Quickplay, Run},
+    systems::gameplay::{score::Score, stats::Stats},
+    utils::random::GameRng,
+};
+
+/// Shared implementation of the results screens
+struct ResultsScreen {
+    title: &'static str,
+    run: Run,
+    /// Next level of the campaign, if the level was finished
+    next: Option<Run>,
+    texts: Vec<Entity>,
+}
+
+impl ResultsScreen {
+    fn new(title: &'static str, run: Run, next: Option<Run>) -> Self {
+        Self {
+            title,
+            run,
+            next,
+            texts: Vec::new(),
+        }
+    }
+
+    fn on_start(&mut self, world: &mut World) {
+        let score = world.read_resource::<Score>().points;
+        let seed = world.read_resource::<GameRng>().seed;
+        info!(
+            "{} on level {} with score {} (seed {})",
+            self.title, self.run.level_name, score, seed
+        );
+        let mut high_scores = HighScores::load();
+        let rank = high_scores.insert(
+            &self.run.level_name,
+            self.run.difficulty,
+            Entry::new(score, seed),
+        );
+        let status = match rank {
+            Some(rank) => match high_scores.save() {
+                Ok(()) => format!("New high score! Rank {}", rank + 1),
+                Err(e) => {
+                    error!("Failed to save high scores: {}", e);
+                    format!("Failed to save high score: {}", e)
+                }
+            },
+            None => String::new(),
+        };
+        let stats = world.read_resource::<Stats>().describe();
+        if let Err(e) = world.read_resource::<Stats>().save(&self.run.level_name) {
+            error!("Failed to save stats: {}", e);
+        }
+        let controls = if self.next.is_some() {
+            "[Enter] Next level  [R] Restart"
+        } else {
+            "[R] Restart"
+        };
+        let font = default_font(world);
+        let white = [1., 1., 1., 1.];
+        self.texts = vec![
+            create_text(world, &font, self.title.to_owned(), 380., 60., white),
+            create_text(
+                world,
+                &font,
+                format!("{} ({})", self.run.level_name, self.run.difficulty.name()),
+                320.,
+                30.,
+                white,
+            ),
+            create_text(world, &font, format!("Score: {}", score), 270., 30., white),
+            create_text(world, &font, status, 230., 22., white),
+            create_text(world, &font, controls.to_owned(), 50., 25., white),
+            create_text(world, &font, "[Esc] Main menu".to_owned(), 10., 25., white),
+            create_text(world, &font, "High scores".to_owned(), -60., 25., white),
+        ];
+        for (i, line) in stats.into_iter().enumerate() {
+            let y = 170. - 30. * i as f32;
+            self.texts
+                .push(create_text(world, &font, line, y, 22., white));
+        }
+        let table = high_scores.table(&self.run.level_name, self.run.difficulty);
+        for (i, entry) in table.iter().enumerate() {
+            let cursor = if Some(i) == rank { "> " } else { "" };
+            let line = format!("{}{}", cursor, entry.describe(i));
+            let y = -100. - 26. * i as f32;
+            self.texts
+                .push(create_text(world, &font, line, y, 20., white));
+        }
+    }
+
+    fn on_stop(&mut self, world: &mut World) {
+        world
+            .delete_entities(&self.texts)
+            .expect("Failed to delete results screen");
+        self.texts.clear();
+    }
+
+    fn handle_event(&self, event: StateEvent) -> SimpleTrans {
+        match event {
+            StateEvent::Window(Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(key),
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            }) => match key {
+                VirtualKeyCode::Return if self.next.is_some() => {
+                    Trans::Switch(Box::new(Quickplay::new(self.next.clone().unwrap())))
+                }
+                VirtualKeyCode::R | VirtualKeyCode::Return => {
+                    Trans::Switch(Box::new(Quickplay::new(self.run.clone())))
+                }
+                VirtualKeyCode::Escape => Trans::Switch(Box::new(MainMenu::default())),
+                _ => Trans::None,
+            },
+            _ => Trans::None,
+        }
+    }
+}
+
+/// Shown when the player loses all their lives
+pub struct GameOver(ResultsScreen);
+
+impl GameOver {
+    pub fn new(run: Run) -> Self {
+        Self(ResultsScreen::new("Game Over", run, None))
+    }
+}
+
+impl SimpleState for GameOver {
+    fn on_start(&mut self, data: StateData<GameData>) {
+        self.0.on_start(data.world);
+    }
+
+    fn on_stop(&mut self, data: StateData<GameData>) {
+        self.0.on_stop(data.world);
+    }
+
+    fn handle_event(&mut self, _data: StateData<GameData>, event: StateEvent) -> SimpleTrans {
+        self.0.handle_event(event)
+    }
+}
+
+/// Shown when the level script ends and all enemies are gone
+pub struct LevelComplete(ResultsScreen);
+
+impl LevelComplete {
+    pub fn new(run: Run) -> Self {
+        let next = run.next();
+        // The last level of the campaign gets its own title
+        let title = match (run.campaign_level, &next) {
+            (Some(_), None) => "Campaign Complete",
+            _ => "Level Complete",
+        };
+        Self(ResultsScreen::new(title, run, next))
+    }
+}
+
+impl SimpleState for LevelComplete {
+    fn on_start(&mut self, data: StateData<GameData>) {
+        self.0.on_start(data.world);
+    }
+
+    fn on_stop(&mut self, data: StateData<GameData>) {
+        self.0.on_stop(data.world);
+    }
+
+    fn handle_event(&mut self, _data: StateData<GameData>, event: StateEvent) -> SimpleTrans {
+        self.0.handle_event(event)
+    }
+}
@@ -0,0 +1,104 @@
+use amethyst::{
+    core::math::Point2,
+    derive::SystemDesc,
+    ecs::{
+        world::Builder, Entities, Entity, Join, LazyUpdate, Read, ReadStorage, System, SystemData,
+        WriteStorage,
+    },
+};
+
+use crate::{
+    components::{Boss, Circle, Color, InScreen, Moving, Rectangle, Transform},
+    display::{HEIGHT as H, WIDTH as W},
+    utils::creator::LazyCreator,
+};
+
+const BAR_MARGIN: f32 = W / 4.;
+const BAR_TOP: f32 = 20.;
+const BAR_HEIGHT: f32 = 12.;
+
+/// Keeps bosses inside the screen and shows the health bar
+#[derive(SystemDesc, Default)]
+pub struct BossSystem {
+    /// The filled and the empty part of the health bar
+    health_bar: Option<(Entity, Entity)>,
+}
+
+/// Rectangles for the filled and empty part of the health bar
+fn health_bar_rectangles(fraction: f32) -> (Rectangle, Rectangle) {
+    let split = BAR_MARGIN + (W - 2. * BAR_MARGIN) * fraction;
+    let bottom = BAR_TOP + BAR_HEIGHT;
+    (
+        Rectangle::new(Point2::new(BAR_MARGIN, BAR_TOP), Point2::new(split, bottom)),
+        Rectangle::new(
+            Point2::new(split, BAR_TOP),
+            Point2::new(W - BAR_MARGIN, bottom),
+        ),
+    )
+}
+
+impl<'s> System<'s> for BossSystem {
+    type SystemData = (
+        Read<'s, LazyUpdate>,
+        Entities<'s>,
+        ReadStorage<'s, Boss>,
+        ReadStorage<'s, Circle>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, InScreen>,
+        WriteStorage<'s, Moving>,
+        WriteStorage<'s, Rectangle>,
+    );
+
+    fn run(
+        &mut self,
+        (lazy, entities, bosses, circles, transforms, in_screens, mut movings, mut rectangles): Self::SystemData,
+    ) {
+        // Bounce on the edges of the screen once inside it
+        for (_boss, circle, transform, moving, _in_screen) in
+            (&bosses, &circles, &transforms, &mut movings, &in_screens).join()
+        {
+            let (c, r, v) = (transform.0, circle.radius, &mut moving.0);
+            if (c.x < r && v.x < 0.) || (c.x > W - r && v.x > 0.) {
+                v.x = -v.x;
+            }
+            if (c.y < r && v.y < 0.) || (c.y > H - r && v.y > 0.) {
+                v.y = -v.y;
+            }
+        }
+
+        match (bosses.join().next(), self.health_bar) {
+            (Some(boss), None) => {
+                let creator = LazyCreator::new(&lazy, &entities);
+                let (filled, empty) = health_bar_rectangles(boss.health_fraction());
+                self.health_bar = Some((
+                    creator
+                        .create_entity()
+                        .with(filled)
+                        .with(Color::rgb(0.8, 0.1, 0.1))
+                        .build(),
+                    creator
+                        .create_entity()
+                        .with(empty)
+                        .with(Color::rgba(0.3, 0.3, 0.3, 0.8))
+                        .build(),
+                ));
+            }
+            (Some(boss), Some((filled_id, empty_id))) => {
+                let (filled, empty) = health_bar_rectangles(boss.health_fraction());
+                // Might not exist yet if they were just created
+                if let Some(r) = rectangles.get_mut(filled_id) {
+                    *r = filled;
+                }
+                if let Some(r) = rectangles.get_mut(empty_id) {
+                    *r = empty;
+                }
+            }
+            (None, Some((filled_id, empty_id))) => {
+                entities.delete(filled_id).unwrap();
+                entities.delete(empty_id).unwrap();
+                self.health_bar = None;
+            }
+            (None, None) => {}
+        }
+    }
+}
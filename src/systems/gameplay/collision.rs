@@ -1,22 +1,31 @@
 use amethyst::{
-    core::math::Rotation2,
+    core::math::{Rotation2, Vector2},
     core::timing::Time,
     derive::SystemDesc,
     ecs::{
         world::Builder, Entities, Join, LazyUpdate, ParJoin, Read, ReadStorage, System, SystemData,
+        Write, WriteStorage,
     },
 };
+use rand::{seq::SliceRandom, Rng};
 use rayon::prelude::*;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicU32, Ordering};
 
 use crate::{
-    components::{circle::collides, BallEnemy, Circle, Color, InScreen, Moving, Shot, Transform},
-    systems::particles::create_explosion,
-    utils::creator::LazyCreator,
+    components::{
+        circle::collides, pickup::PICKUP_SPEED, BallEnemy, Boss, Circle, Color, InScreen, Moving,
+        Pickup, Shot, Transform,
+    },
+    config::GameplayConfig,
+    systems::{
+        gameplay::{psycho::PsychoMeter, score::Score, stats::Stats},
+        particles::create_explosion,
+    },
+    utils::{creator::LazyCreator, random::GameRng},
 };
 
 impl BallEnemy {
-    fn on_destroy(
+    pub fn on_destroy(
         &self,
         time: &Time,
         creator: &LazyCreator,
@@ -27,7 +36,7 @@ impl BallEnemy {
     ) {
         create_explosion(&time, &creator, transform.0, circle.radius, 25, color);
         match self {
-            BallEnemy::Simple => {}
+            BallEnemy::Simple | BallEnemy::Obstacle => {}
             BallEnemy::Double => {
                 for i in 0..2 {
                     let rot = Rotation2::new((30. * (i as f32) - 15.).to_radians());
@@ -36,7 +45,7 @@ impl BallEnemy {
                         Circle {
                             radius: circle.radius * 0.6,
                         },
-                        Transform::from(transform.0 + moving.0 * time.fixed_seconds()),
+                        Transform::from(transform.0 + moving.0 * time.delta_seconds()),
                         Moving::from(rot * moving.0),
                     );
                 }
@@ -53,18 +62,41 @@ impl<'s> System<'s> for CollisionSystem {
         Read<'s, Time>,
         Read<'s, LazyUpdate>,
         Entities<'s>,
-        ReadStorage<'s, Shot>,
+        WriteStorage<'s, Shot>,
         ReadStorage<'s, BallEnemy>,
         ReadStorage<'s, Transform>,
         ReadStorage<'s, Moving>,
         ReadStorage<'s, Circle>,
         ReadStorage<'s, InScreen>,
         ReadStorage<'s, Color>,
+        WriteStorage<'s, Boss>,
+        Read<'s, GameplayConfig>,
+        Write<'s, Score>,
+        Write<'s, GameRng>,
+        Write<'s, Stats>,
+        Write<'s, PsychoMeter>,
     );
 
     fn run(
         &mut self,
-        (time, lazy, entities, shots, enemies, transforms, movings, circles, in_screens, colors): Self::SystemData,
+        (
+            time,
+            lazy,
+            entities,
+            mut shots,
+            enemies,
+            transforms,
+            movings,
+            circles,
+            in_screens,
+            colors,
+            mut bosses,
+            config,
+            mut score,
+            mut rng,
+            mut stats,
+            mut meter,
+        ): Self::SystemData,
     ) {
         let enemies = (
             &entities,
@@ -76,12 +108,13 @@ impl<'s> System<'s> for CollisionSystem {
             &colors,
         )
             .join()
-            .map(|x| (AtomicBool::new(false), x))
+            .map(|x| (AtomicU32::new(0), x))
             .collect::<Vec<_>>();
         let creator = LazyCreator::new(&lazy, &entities);
+        let shots_hit = AtomicU32::new(0);
         (
             &entities,
-            &shots,
+            &mut shots,
             &transforms,
             &circles,
             &in_screens,
@@ -91,20 +124,50 @@ impl<'s> System<'s> for CollisionSystem {
             // If this really becomes a problem, there are faster ways to
             // implement this collision
             .par_join()
-            .for_each(|(s_id, _shot, s_t, s_c, _in_screen, s_color)| {
-                for (dead, (e_id, _enemy, e_t, _e_m, e_c, _in_screen, _e_color)) in enemies.iter() {
-                    if collides(e_t, e_c, s_t, s_c, 0.) {
+            .for_each(|(s_id, shot, s_t, s_c, _in_screen, s_color)| {
+                for (hits, (e_id, enemy, e_t, _e_m, e_c, _in_screen, _e_color)) in enemies.iter() {
+                    if shot.hit.contains(e_id) || !collides(e_t, e_c, s_t, s_c, 0.) {
+                        continue;
+                    }
+                    create_explosion(&time, &creator, s_t.0, s_c.radius, 10, s_color);
+                    hits.fetch_add(shot.damage, Ordering::Relaxed);
+                    if shot.hit.is_empty() {
+                        shots_hit.fetch_add(1, Ordering::Relaxed);
+                    }
+                    shot.hit.push(*e_id);
+                    // Obstacles stop even piercing shots
+                    if shot.piercing == 0 || matches!(enemy, BallEnemy::Obstacle) {
                         entities.delete(s_id).unwrap();
-                        create_explosion(&time, &creator, s_t.0, s_c.radius, 10, s_color);
-                        dead.store(true, Ordering::Relaxed);
                         break;
                     }
+                    shot.piercing -= 1;
                 }
             });
-        for (dead, (e_id, enemy, e_t, e_m, e_c, _, e_color)) in enemies {
-            if dead.into_inner() {
+        stats.hits += shots_hit.into_inner();
+        for (hits, (e_id, enemy, e_t, e_m, e_c, _, e_color)) in enemies {
+            let hits = hits.into_inner();
+            // Obstacles absorb shots without dying
+            if hits == 0 || matches!(enemy, BallEnemy::Obstacle) {
+                continue;
+            }
+            // Bosses only die when they run out of health
+            let dead = bosses
+                .get_mut(e_id)
+                .map(|boss| boss.damage(hits))
+                .unwrap_or(true);
+            if dead {
                 entities.delete(e_id).unwrap();
                 enemy.on_destroy(&time, &creator, e_t, e_m, e_c, e_color);
+                let points = bosses
+                    .get(e_id)
+                    .map_or_else(|| enemy.points(e_c.radius), Boss::points);
+                score.add_kill(points, time.absolute_time_seconds(), &config);
+                stats.add_kill(enemy, bosses.contains(e_id));
+                meter.fill(config.kill_meter);
+                if rng.rng.gen::<f32>() < config.pickup_chance {
+                    let pickup = *Pickup::ALL.choose(&mut rng.rng).unwrap();
+                    creator.create_pickup(pickup, e_t.0, Vector2::new(0., PICKUP_SPEED));
+                }
             }
         }
     }
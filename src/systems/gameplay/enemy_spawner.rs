@@ -1,14 +1,13 @@
 use amethyst::{
-    core::math::{Point2, Vector2},
+    core::math::Vector2,
     core::timing::Time,
     derive::SystemDesc,
     ecs::{Entities, Join, LazyUpdate, Read, ReadStorage, System, SystemData, WriteStorage},
 };
 
 use crate::{
-    components::{EnemySpawner, Triangle},
+    components::{living_players, EnemySpawner, Player, PlayerState, Transform, Triangle},
     display::{HEIGHT as H, WIDTH as W},
-    systems::player::movement::PlayerPosition,
     utils::creator::LazyCreator,
 };
 
@@ -19,11 +18,11 @@ const MARGIN: f32 = 20.;
 const SIZE: f32 = 18.;
 
 impl EnemySpawner {
-    fn adjust_indicator(&self, triangle: &mut Triangle, player_pos: Point2<f32>) {
+    fn adjust_indicator(&self, triangle: &mut Triangle, players: &[PlayerState]) {
         let mut center = self.position;
         center.x = center.x.clamp(MARGIN, W - MARGIN);
         center.y = center.y.clamp(MARGIN, H - MARGIN);
-        let unit = self.calc_speed(player_pos).normalize();
+        let unit = self.calc_speed(players).normalize();
         // Perpendicular to unit
         let perp = Vector2::new(-unit.y, unit.x);
         triangle.vertices = [
@@ -42,23 +41,25 @@ impl<'s> System<'s> for EnemySpawnerSystem {
         Entities<'s>,
         ReadStorage<'s, EnemySpawner>,
         WriteStorage<'s, Triangle>,
-        Read<'s, PlayerPosition>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Transform>,
     );
 
     fn run(
         &mut self,
-        (time, lazy, entities, spawners, mut triangles, player_pos): Self::SystemData,
+        (time, lazy, entities, spawners, mut triangles, players, transforms): Self::SystemData,
     ) {
+        let players = living_players(&players, &transforms);
         let creator = LazyCreator {
             lazy: &lazy,
             entities: &entities,
         };
         for (id, spawner, mut triangle) in (&entities, &spawners, &mut triangles).join() {
             if spawner.spawn_at <= time.absolute_time_seconds() {
-                spawner.do_spawn(&creator, player_pos.0);
+                spawner.do_spawn(&creator, &players);
                 entities.delete(id);
             } else {
-                spawner.adjust_indicator(&mut triangle, player_pos.0);
+                spawner.adjust_indicator(&mut triangle, &players);
             }
         }
     }
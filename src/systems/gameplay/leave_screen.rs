@@ -1,11 +1,12 @@
 use amethyst::{
     derive::SystemDesc,
-    ecs::{Entities, Join, LazyUpdate, Read, ReadStorage, System, SystemData},
+    ecs::{Entities, Join, LazyUpdate, Read, ReadStorage, System, SystemData, Write},
 };
 
 use crate::{
-    components::{BallEnemy, Circle, InScreen, Shot, Transform},
+    components::{BallEnemy, Circle, InScreen, Pickup, Shot, Transform},
     display::{HEIGHT as H, WIDTH as W},
+    systems::gameplay::stats::Stats,
 };
 
 #[derive(SystemDesc, Default)]
@@ -16,22 +17,24 @@ impl<'s> System<'s> for LeaveScreenSystem {
         Entities<'s>,
         ReadStorage<'s, Shot>,
         ReadStorage<'s, BallEnemy>,
+        ReadStorage<'s, Pickup>,
         ReadStorage<'s, InScreen>,
         ReadStorage<'s, Transform>,
         ReadStorage<'s, Circle>,
         Read<'s, LazyUpdate>,
+        Write<'s, Stats>,
     );
 
-    /// If the circle (enemy or shot) leaves the screen, kills it, but only if
+    /// If the circle (enemy, shot or pickup) leaves the screen, kills it, but only if
     /// it has entered the screen in the past (this allows enemies being spawned
     /// outside the screen)
     fn run(
         &mut self,
-        (entities, shots, enemies, in_screens, transforms, circles, lazy): Self::SystemData,
+        (entities, shots, enemies, pickups, in_screens, transforms, circles, lazy, mut stats): Self::SystemData,
     ) {
         for (entity, _, in_screen, transform, circle) in (
             &entities,
-            shots.mask() | enemies.mask(),
+            shots.mask() | enemies.mask() | pickups.mask(),
             (&in_screens).maybe(),
             &transforms,
             &circles,
@@ -45,6 +48,12 @@ impl<'s> System<'s> for LeaveScreenSystem {
                 // kill if outside screen
                 if outside_screen {
                     entities.delete(entity).unwrap();
+                    // Obstacles are meant to be dodged, not killed
+                    if let Some(enemy) = enemies.get(entity) {
+                        if !matches!(enemy, BallEnemy::Obstacle) {
+                            stats.escaped += 1;
+                        }
+                    }
                 }
             } else {
                 // mark if inside screen
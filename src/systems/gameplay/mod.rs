@@ -1,7 +1,20 @@
+mod boss;
 mod collision;
 mod enemy_spawner;
 mod leave_screen;
+mod orbit;
+pub mod play_area;
+pub mod practice;
+pub mod psycho;
+pub mod score;
+pub mod slow_motion;
+pub mod stats;
 
+pub use boss::BossSystem;
 pub use collision::CollisionSystem;
 pub use enemy_spawner::EnemySpawnerSystem;
 pub use leave_screen::LeaveScreenSystem;
+pub use orbit::OrbitSystem;
+pub use play_area::PlayAreaSystem;
+pub use psycho::PsychoSystem;
+pub use slow_motion::SlowMotionSystem;
@@ -0,0 +1,70 @@
+use amethyst::{
+    core::timing::Time,
+    derive::SystemDesc,
+    ecs::{Entities, Join, LazyUpdate, Read, ReadStorage, System, SystemData, WriteStorage},
+};
+
+use crate::{
+    components::{transform::SatelliteFate, Circle, Color, Moving, Orbit, Transform},
+    systems::particles::create_explosion,
+    utils::creator::LazyCreator,
+};
+
+#[derive(SystemDesc, Default)]
+pub struct OrbitSystem;
+
+impl<'s> System<'s> for OrbitSystem {
+    type SystemData = (
+        Read<'s, Time>,
+        Read<'s, LazyUpdate>,
+        Entities<'s>,
+        WriteStorage<'s, Orbit>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, Moving>,
+        ReadStorage<'s, Circle>,
+        ReadStorage<'s, Color>,
+    );
+
+    /// Moves satellites around their parents. If the parent is dead, the
+    /// satellite is either released or destroyed, depending on its fate.
+    fn run(
+        &mut self,
+        (time, lazy, entities, mut orbits, mut transforms, mut movings, circles, colors): Self::SystemData,
+    ) {
+        let creator = LazyCreator::new(&lazy, &entities);
+        let dt = time.delta_seconds();
+        let mut orphans = Vec::new();
+        for (entity, orbit) in (&entities, &mut orbits).join() {
+            // Storages only return components of living entities
+            let parent = transforms
+                .get(orbit.parent)
+                .map(|t| t.0)
+                .zip(movings.get(orbit.parent).map(|m| m.0));
+            if let Some((parent_pos, parent_speed)) = parent {
+                orbit.angle += orbit.angular_speed * dt;
+                if let Some(transform) = transforms.get_mut(entity) {
+                    transform.0 = parent_pos + orbit.offset();
+                }
+                // Not used to move, but keeps the speed right for when it is released
+                if let Some(moving) = movings.get_mut(entity) {
+                    moving.0 = parent_speed + orbit.tangential_speed();
+                }
+            } else {
+                orphans.push((entity, orbit.fate));
+            }
+        }
+        for (entity, fate) in orphans {
+            orbits.remove(entity);
+            if fate == SatelliteFate::Destroy {
+                entities.delete(entity).unwrap();
+                if let (Some(t), Some(c), Some(color)) = (
+                    transforms.get(entity),
+                    circles.get(entity),
+                    colors.get(entity),
+                ) {
+                    create_explosion(&time, &creator, t.0, c.radius, 10, color);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use amethyst::{
+    core::math::Point2,
+    derive::SystemDesc,
+    ecs::{world::Builder, Entities, Entity, LazyUpdate, Read, System, SystemData, WriteStorage},
+};
+
+use crate::{
+    components::{Color, Rectangle},
+    display::{HEIGHT as H, WIDTH as W},
+    utils::creator::LazyCreator,
+};
+
+/// Rectangle the player is allowed to move in. Levels may change it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayArea {
+    pub min: Point2<f32>,
+    pub max: Point2<f32>,
+}
+
+impl Default for PlayArea {
+    fn default() -> Self {
+        Self {
+            min: Point2::new(0., 0.),
+            max: Point2::new(W, H),
+        }
+    }
+}
+
+impl PlayArea {
+    pub fn center(&self) -> Point2<f32> {
+        Point2::from((self.min.coords + self.max.coords) / 2.)
+    }
+
+    /// Closest point to `p` that is at least `margin` away from the borders.
+    /// If the area is too small, prefers the top left.
+    pub fn clamp(&self, p: Point2<f32>, margin: f32) -> Point2<f32> {
+        Point2::new(
+            p.x.min(self.max.x - margin).max(self.min.x + margin),
+            p.y.min(self.max.y - margin).max(self.min.y + margin),
+        )
+    }
+
+    /// Rectangles covering the parts of the screen outside the area
+    fn outside(&self) -> [Rectangle; 4] {
+        let (a, b) = (self.min, self.max);
+        [
+            Rectangle::new(Point2::new(0., 0.), Point2::new(W, a.y)),
+            Rectangle::new(Point2::new(0., b.y), Point2::new(W, H)),
+            Rectangle::new(Point2::new(0., a.y), Point2::new(a.x, b.y)),
+            Rectangle::new(Point2::new(b.x, a.y), Point2::new(W, b.y)),
+        ]
+    }
+}
+
+/// Darkens the screen outside the play area
+#[derive(SystemDesc, Default)]
+pub struct PlayAreaSystem {
+    shades: Option<Vec<Entity>>,
+}
+
+impl<'s> System<'s> for PlayAreaSystem {
+    type SystemData = (
+        Read<'s, LazyUpdate>,
+        Entities<'s>,
+        Read<'s, PlayArea>,
+        WriteStorage<'s, Rectangle>,
+    );
+
+    fn run(&mut self, (lazy, entities, area, mut rectangles): Self::SystemData) {
+        let outside = area.outside();
+        if let Some(shades) = &self.shades {
+            for (shade, rectangle) in shades.iter().zip(outside.iter()) {
+                // Might not exist yet if they were just created
+                if let Some(r) = rectangles.get_mut(*shade) {
+                    *r = rectangle.clone();
+                }
+            }
+        } else {
+            let creator = LazyCreator::new(&lazy, &entities);
+            self.shades = Some(
+                outside
+                    .iter()
+                    .map(|rectangle| {
+                        creator
+                            .create_entity()
+                            .with(rectangle.clone())
+                            .with(Color::rgba(0.25, 0.25, 0.25, 0.5))
+                            .build()
+                    })
+                    .collect(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp() {
+        let area = PlayArea {
+            min: Point2::new(100., 200.),
+            max: Point2::new(400., 600.),
+        };
+        assert_eq!(area.center(), Point2::new(250., 400.));
+        // Points are kept `margin` away from the borders
+        assert_eq!(
+            area.clamp(Point2::new(0., 1000.), 10.),
+            Point2::new(110., 590.)
+        );
+        assert_eq!(
+            area.clamp(Point2::new(200., 300.), 10.),
+            Point2::new(200., 300.)
+        );
+    }
+}
@@ -0,0 +1,26 @@
+/// Game speeds practice mode can run at
+pub const PRACTICE_SPEEDS: [f32; 3] = [1., 0.75, 0.5];
+
+/// Settings of a practice run. Normal runs use the default, which changes nothing.
+#[derive(Debug, Clone)]
+pub struct Practice {
+    /// Level events skipped before playing
+    pub start: usize,
+    /// Whether the run restarts when the level reaches the next checkpoint, to
+    /// loop a section. It also restarts when a player dies.
+    pub loop_section: bool,
+    pub invincible: bool,
+    /// Multiplies the game speed
+    pub speed: f32,
+}
+
+impl Default for Practice {
+    fn default() -> Self {
+        Self {
+            start: 0,
+            loop_section: false,
+            invincible: false,
+            speed: 1.,
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use amethyst::{
+    core::timing::Time,
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadStorage, System, SystemData, Write},
+    input::InputHandler,
+};
+
+use crate::{
+    components::Player,
+    config::GameplayConfig,
+    input::{ActionBinding, PsychoBindingTypes},
+};
+
+/// Charge of the psycho meter, shared by all players
+#[derive(Debug, Default)]
+pub struct PsychoMeter {
+    /// From 0 (empty) to 1 (full)
+    pub charge: f32,
+    /// Whether it's being spent, slowing the game down
+    pub active: bool,
+}
+
+impl PsychoMeter {
+    pub fn fill(&mut self, amount: f32) {
+        self.charge = (self.charge + amount).min(1.);
+    }
+}
+
+/// Spends the psycho meter while any living player holds its button
+#[derive(SystemDesc, Default)]
+pub struct PsychoSystem;
+
+impl<'s> System<'s> for PsychoSystem {
+    type SystemData = (
+        Read<'s, Time>,
+        Read<'s, InputHandler<PsychoBindingTypes>>,
+        ReadStorage<'s, Player>,
+        Read<'s, GameplayConfig>,
+        Write<'s, PsychoMeter>,
+    );
+
+    fn run(&mut self, (time, input, players, config, mut meter): Self::SystemData) {
+        let held = players.join().any(|player| {
+            input
+                .action_is_down(&ActionBinding::Psycho(player.id))
+                .unwrap_or(false)
+        });
+        meter.active = held && meter.charge > 0.;
+        if meter.active {
+            // Real time, so the meter lasts as long whatever the game speed
+            meter.charge = (meter.charge - config.psycho_drain * time.delta_real_seconds()).max(0.);
+        }
+    }
+}
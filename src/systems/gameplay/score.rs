@@ -0,0 +1,137 @@
+use crate::{
+    components::{BallEnemy, Boss},
+    config::GameplayConfig,
+};
+
+/// Radius of enemies worth their base points. Smaller ones are worth more.
+const BASE_RADIUS: f32 = 20.;
+
+impl BallEnemy {
+    /// Points for killing an enemy of this kind with the given radius
+    pub fn points(&self, radius: f32) -> u64 {
+        let base = match self {
+            BallEnemy::Simple => 10.,
+            BallEnemy::Double => 25.,
+            BallEnemy::Obstacle => 0.,
+        };
+        (base * (BASE_RADIUS / radius).clamp(0.5, 3.)).round() as u64
+    }
+}
+
+impl Boss {
+    pub fn points(&self) -> u64 {
+        self.max_health as u64 * 20
+    }
+}
+
+/// Points scored in the current level, shared by all players
+#[derive(Debug, Default)]
+pub struct Score {
+    pub points: u64,
+    /// Kills in the current combo
+    pub combo: u32,
+    /// When the current combo ends if there are no more kills
+    pub combo_until: f64,
+}
+
+impl Score {
+    /// Multiplier applied to kills right now
+    pub fn multiplier(&self, now: f64, config: &GameplayConfig) -> u32 {
+        if now > self.combo_until {
+            1
+        } else {
+            (1 + self.combo / config.combo_step.max(1)).min(config.max_multiplier)
+        }
+    }
+
+    /// Awards the points for a kill, multiplied by the combo
+    pub fn add_kill(&mut self, points: u64, now: f64, config: &GameplayConfig) {
+        if now > self.combo_until {
+            self.combo = 0;
+        }
+        self.combo += 1;
+        self.combo_until = now + config.combo_time;
+        self.points += points * self.multiplier(now, config) as u64;
+    }
+
+    /// Called when a player is hit
+    pub fn break_combo(&mut self) {
+        self.combo = 0;
+        self.combo_until = 0.;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(combo_step: u32, max_multiplier: u32) -> GameplayConfig {
+        GameplayConfig {
+            combo_time: 2.,
+            combo_step,
+            max_multiplier,
+            ..GameplayConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_combo_multiplier() {
+        let config = config(2, 8);
+        let mut score = Score::default();
+        score.add_kill(10, 0., &config);
+        assert_eq!(score.points, 10);
+        score.add_kill(10, 1., &config);
+        assert_eq!(score.points, 30);
+        assert_eq!(score.multiplier(1., &config), 2);
+        // Still going right until the combo time is over
+        assert_eq!(score.multiplier(3., &config), 2);
+    }
+
+    #[test]
+    fn test_combo_expires() {
+        let config = config(2, 8);
+        let mut score = Score::default();
+        score.add_kill(10, 0., &config);
+        score.add_kill(10, 1., &config);
+        assert_eq!(score.multiplier(3.5, &config), 1);
+        score.add_kill(10, 3.5, &config);
+        assert_eq!(score.combo, 1);
+        assert_eq!(score.points, 40);
+    }
+
+    #[test]
+    fn test_max_multiplier() {
+        let config = config(1, 3);
+        let mut score = Score::default();
+        for i in 0..5 {
+            score.add_kill(10, i as f64, &config);
+        }
+        assert_eq!(score.combo, 5);
+        assert_eq!(score.multiplier(4., &config), 3);
+        assert_eq!(score.points, 10 * (2 + 3 + 3 + 3 + 3));
+    }
+
+    #[test]
+    fn test_zero_combo_step() {
+        // Works like a step of 1 instead of dividing by zero
+        let config = config(0, 8);
+        let mut score = Score::default();
+        score.add_kill(10, 0., &config);
+        score.add_kill(10, 0., &config);
+        assert_eq!(score.multiplier(0., &config), 3);
+        assert_eq!(score.points, 20 + 30);
+    }
+
+    #[test]
+    fn test_break_combo() {
+        let config = config(1, 8);
+        let mut score = Score::default();
+        score.add_kill(10, 0., &config);
+        score.add_kill(10, 0.5, &config);
+        score.break_combo();
+        assert_eq!(score.multiplier(1., &config), 1);
+        score.add_kill(10, 1., &config);
+        assert_eq!(score.combo, 1);
+        assert_eq!(score.points, 20 + 30 + 20);
+    }
+}
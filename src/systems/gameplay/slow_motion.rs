@@ -0,0 +1,48 @@
+use amethyst::{
+    core::timing::Time,
+    derive::SystemDesc,
+    ecs::{Read, System, SystemData, Write},
+};
+
+use crate::{
+    config::GameplayConfig,
+    systems::gameplay::{practice::Practice, psycho::PsychoMeter},
+};
+
+/// Until when (in real seconds) the game runs in slow motion
+#[derive(Debug, Default)]
+pub struct SlowMotion {
+    pub until: Option<f64>,
+}
+
+/// Scales the game time while slow motion or the psycho meter is active, on top
+/// of the practice mode speed
+#[derive(SystemDesc, Default)]
+pub struct SlowMotionSystem;
+
+impl<'s> System<'s> for SlowMotionSystem {
+    type SystemData = (
+        Write<'s, Time>,
+        Write<'s, SlowMotion>,
+        Read<'s, GameplayConfig>,
+        Read<'s, Practice>,
+        Read<'s, PsychoMeter>,
+    );
+
+    fn run(&mut self, (mut time, mut slow_motion, config, practice, meter): Self::SystemData) {
+        let scale = match slow_motion.until {
+            Some(until) if time.absolute_real_time_seconds() < until => config.slow_motion_scale,
+            Some(_) => {
+                slow_motion.until = None;
+                1.
+            }
+            None => 1.,
+        };
+        let scale = if meter.active {
+            scale.min(config.psycho_scale)
+        } else {
+            scale
+        };
+        time.set_time_scale(scale * practice.speed);
+    }
+}
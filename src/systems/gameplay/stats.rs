@@ -0,0 +1,104 @@
+use chrono::Local;
+use failure::Error;
+use log::info;
+use serde::Serialize;
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use crate::{components::BallEnemy, utils::fs::user_dir};
+
+/// What happened during the current run, shown on the results screen
+#[derive(Debug, Default, Serialize)]
+pub struct Stats {
+    pub shots_fired: u32,
+    /// Shots that hit at least one enemy
+    pub hits: u32,
+    /// Set when the run ends
+    pub accuracy: f32,
+    /// Kills by kind of enemy, bosses are counted separately
+    pub kills: BTreeMap<String, u32>,
+    /// Enemies that left the screen alive
+    pub escaped: u32,
+    /// Enemies that passed close to a player without hitting them
+    pub grazes: u32,
+    pub deaths: u32,
+    pub bombs_used: u32,
+    /// In real seconds, so slow motion doesn't inflate it. Set when the run ends
+    pub time_survived: f64,
+    #[serde(skip)]
+    started_at: f64,
+}
+
+impl Stats {
+    pub fn new(now: f64) -> Self {
+        Self {
+            started_at: now,
+            ..Self::default()
+        }
+    }
+
+    pub fn add_kill(&mut self, enemy: &BallEnemy, boss: bool) {
+        let kind = if boss {
+            "Boss".to_owned()
+        } else {
+            format!("{:?}", enemy)
+        };
+        *self.kills.entry(kind).or_insert(0) += 1;
+    }
+
+    /// Called when the level is complete or the last life is lost
+    pub fn finish(&mut self, now: f64) {
+        self.time_survived = now - self.started_at;
+        self.accuracy = if self.shots_fired == 0 {
+            0.
+        } else {
+            self.hits as f32 / self.shots_fired as f32
+        };
+    }
+
+    /// Lines of text for the results screen
+    pub fn describe(&self) -> Vec<String> {
+        let kills = if self.kills.is_empty() {
+            "none".to_owned()
+        } else {
+            self.kills
+                .iter()
+                .map(|(kind, n)| format!("{} {}", kind, n))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let seconds = self.time_survived as u64;
+        vec![
+            format!(
+                "Shots {}  Hits {}  Accuracy {:.0}%",
+                self.shots_fired,
+                self.hits,
+                self.accuracy * 100.
+            ),
+            format!(
+                "Kills: {}  Escaped {}  Grazes {}",
+                kills, self.escaped, self.grazes
+            ),
+            format!(
+                "Deaths {}  Bombs {}  Time {}:{:02}",
+                self.deaths,
+                self.bombs_used,
+                seconds / 60,
+                seconds % 60
+            ),
+        ]
+    }
+
+    /// Writes the statistics to a new JSON file in the user directory
+    pub fn save(&self, level_name: &str) -> Result<PathBuf, Error> {
+        let dir = user_dir().join("stats");
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!(
+            "{}_{}.json",
+            level_name,
+            Local::now().format("%Y%m%d_%H%M%S")
+        ));
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        info!("Saved stats to {:?}", path);
+        Ok(path)
+    }
+}
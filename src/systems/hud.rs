@@ -0,0 +1,190 @@
+use amethyst::{
+    assets::{AssetStorage, Loader},
+    core::{
+        math::{Point2, Vector2},
+        timing::Time,
+    },
+    derive::SystemDesc,
+    ecs::{
+        world::Builder, Entities, Entity, LazyUpdate, Read, ReadExpect, System, SystemData,
+        WriteStorage,
+    },
+    ui::{get_default_font, Anchor, FontAsset, UiText},
+};
+
+use crate::{
+    components::{Circle, Color, Player, Rectangle, Ring, Transform},
+    config::GameplayConfig,
+    display::{text::text_components, HEIGHT as H},
+    systems::{
+        gameplay::{psycho::PsychoMeter, score::Score},
+        player::{bomb::Bombs, respawn::Lives},
+    },
+    utils::creator::LazyCreator,
+};
+
+const ICON_RADIUS: f32 = 8.;
+const ICON_SPACING: f32 = 24.;
+const MARGIN: f32 = 20.;
+const METER_WIDTH: f32 = 120.;
+const METER_HEIGHT: f32 = 8.;
+
+/// Shows the remaining lives, bombs and the psycho meter on the bottom left of
+/// the screen, and the score on the top right
+#[derive(SystemDesc, Default)]
+pub struct HudSystem {
+    lives: Vec<Entity>,
+    bombs: Vec<Entity>,
+    score: Option<Entity>,
+    /// The filled and the empty part of the psycho meter
+    meter: Option<(Entity, Entity)>,
+}
+
+/// Rectangles for the filled and empty part of the psycho meter
+fn meter_rectangles(charge: f32) -> (Rectangle, Rectangle) {
+    let left = MARGIN - ICON_RADIUS;
+    let top = H - MARGIN - 2. * ICON_SPACING - METER_HEIGHT / 2.;
+    let (split, bottom) = (left + METER_WIDTH * charge, top + METER_HEIGHT);
+    (
+        Rectangle::new(Point2::new(left, top), Point2::new(split, bottom)),
+        Rectangle::new(
+            Point2::new(split, top),
+            Point2::new(left + METER_WIDTH, bottom),
+        ),
+    )
+}
+
+/// Creates or deletes icons until there are `amount` of them, placed in a row
+/// starting at `start`
+fn sync_icons(
+    icons: &mut Vec<Entity>,
+    amount: usize,
+    start: Point2<f32>,
+    creator: &LazyCreator,
+    color: &Color,
+    ring: Option<Ring>,
+) {
+    while icons.len() > amount {
+        creator.entities.delete(icons.pop().unwrap()).unwrap();
+    }
+    while icons.len() < amount {
+        let pos = start + Vector2::new(ICON_SPACING * icons.len() as f32, 0.);
+        let builder = creator
+            .create_entity()
+            .with(Circle::with_radius(ICON_RADIUS))
+            .with(color.clone())
+            .with(Transform::from(pos));
+        icons.push(match &ring {
+            Some(ring) => builder.with(ring.clone()).build(),
+            None => builder.build(),
+        });
+    }
+}
+
+impl<'s> System<'s> for HudSystem {
+    type SystemData = (
+        Read<'s, LazyUpdate>,
+        Entities<'s>,
+        Read<'s, Lives>,
+        Read<'s, Bombs>,
+        Read<'s, Time>,
+        Read<'s, Score>,
+        Read<'s, GameplayConfig>,
+        ReadExpect<'s, Loader>,
+        Read<'s, AssetStorage<FontAsset>>,
+        WriteStorage<'s, UiText>,
+        Read<'s, PsychoMeter>,
+        WriteStorage<'s, Rectangle>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            lazy,
+            entities,
+            lives,
+            bombs,
+            time,
+            score,
+            config,
+            loader,
+            fonts,
+            mut ui_texts,
+            meter,
+            mut rectangles,
+        ): Self::SystemData,
+    ) {
+        let creator = LazyCreator::new(&lazy, &entities);
+        let multiplier = score.multiplier(time.absolute_time_seconds(), &config);
+        let text = if multiplier > 1 {
+            format!("{}  x{}", score.points, multiplier)
+        } else {
+            score.points.to_string()
+        };
+        match self.score.and_then(|entity| ui_texts.get_mut(entity)) {
+            Some(ui_text) => ui_text.text = text,
+            // Might not exist yet if it was just created
+            None if self.score.is_some() => {}
+            None => {
+                let font = get_default_font(&loader, &fonts);
+                let (transform, ui_text) = text_components(
+                    &font,
+                    text,
+                    Anchor::TopRight,
+                    (-MARGIN, -MARGIN),
+                    30.,
+                    [1., 1., 1., 1.],
+                );
+                self.score = Some(
+                    creator
+                        .create_entity()
+                        .with(transform)
+                        .with(ui_text)
+                        .build(),
+                );
+            }
+        }
+        sync_icons(
+            &mut self.lives,
+            lives.remaining as usize,
+            Point2::new(MARGIN, H - MARGIN),
+            &creator,
+            &Player::color(0),
+            None,
+        );
+        sync_icons(
+            &mut self.bombs,
+            bombs.remaining as usize,
+            Point2::new(MARGIN, H - MARGIN - ICON_SPACING),
+            &creator,
+            &Color::rgb(1., 0.6, 0.1),
+            Some(Ring(0.5)),
+        );
+        let (filled, empty) = meter_rectangles(meter.charge);
+        match self.meter {
+            Some((filled_id, empty_id)) => {
+                // Might not exist yet if they were just created
+                if let Some(r) = rectangles.get_mut(filled_id) {
+                    *r = filled;
+                }
+                if let Some(r) = rectangles.get_mut(empty_id) {
+                    *r = empty;
+                }
+            }
+            None => {
+                self.meter = Some((
+                    creator
+                        .create_entity()
+                        .with(filled)
+                        .with(Color::rgb(0.6, 0.2, 0.9))
+                        .build(),
+                    creator
+                        .create_entity()
+                        .with(empty)
+                        .with(Color::rgba(0.3, 0.3, 0.3, 0.8))
+                        .build(),
+                ));
+            }
+        }
+    }
+}
@@ -1,6 +1,8 @@
 pub mod gameplay;
+mod hud;
 mod moving;
 pub mod particles;
 pub mod player;
 
+pub use hud::HudSystem;
 pub use moving::MovingSystem;
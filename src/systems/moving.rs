@@ -1,6 +1,7 @@
 use amethyst::{
+    core::timing::Time,
     derive::SystemDesc,
-    ecs::{Join, ReadStorage, System, SystemData, WriteStorage},
+    ecs::{Join, Read, ReadStorage, System, SystemData, WriteStorage},
 };
 
 use crate::components::{Moving, Transform};
@@ -9,11 +10,16 @@ use crate::components::{Moving, Transform};
 pub struct MovingSystem;
 
 impl<'s> System<'s> for MovingSystem {
-    type SystemData = (ReadStorage<'s, Moving>, WriteStorage<'s, Transform>);
+    type SystemData = (
+        Read<'s, Time>,
+        ReadStorage<'s, Moving>,
+        WriteStorage<'s, Transform>,
+    );
 
-    fn run(&mut self, (movings, mut transforms): Self::SystemData) {
+    fn run(&mut self, (time, movings, mut transforms): Self::SystemData) {
+        let dt = time.delta_seconds();
         for (moving, transform) in (&movings, &mut transforms).join() {
-            transform.0 += moving.0;
+            transform.0 += moving.0 * dt;
         }
     }
 }
@@ -19,6 +19,27 @@ use crate::{
     utils::creator::LazyCreator,
 };
 
+/// Leaves behind a fading copy of a circle
+pub fn create_trail(
+    time: &Time,
+    creator: &LazyCreator,
+    center: Point2<f32>,
+    radius: f32,
+    color: &Color,
+) {
+    creator
+        .create_entity()
+        .with(Transform::from(center))
+        .with(Moving::from(Vector2::new(0., 0.)))
+        .with(Circle::with_radius(radius))
+        .with(color.clone())
+        .with(Particle {
+            created: time.absolute_time().as_secs_f32(),
+            lifetime: 0.25,
+        })
+        .build();
+}
+
 pub fn create_explosion(
     time: &Time,
     creator: &LazyCreator,
@@ -38,7 +59,7 @@ pub fn create_explosion(
         creator
             .create_entity()
             .with(Transform::from(center + unit1 * radius * 0.75))
-            .with(Moving::from(unit2 * (1.5 + rnd() * 1.5)))
+            .with(Moving::from(unit2 * (90. + rnd() * 90.)))
             .with(Circle::with_radius(2.))
             .with(color.clone())
             .with(Particle {
@@ -0,0 +1,124 @@
+use amethyst::{
+    core::timing::Time,
+    derive::SystemDesc,
+    ecs::{Entities, Join, LazyUpdate, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+    input::InputHandler,
+};
+
+use crate::{
+    components::{BallEnemy, Boss, Circle, Color, InScreen, Moving, Player, Transform},
+    config::GameplayConfig,
+    input::{ActionBinding, PsychoBindingTypes},
+    systems::{
+        gameplay::{psycho::PsychoMeter, score::Score, stats::Stats},
+        particles::create_explosion,
+    },
+    utils::creator::LazyCreator,
+};
+
+/// Bombs the player has left in this life
+#[derive(Debug, Default)]
+pub struct Bombs {
+    pub remaining: u8,
+}
+
+/// Destroys every enemy on screen when the player uses a bomb, obstacles
+/// included. Bosses only take some damage.
+#[derive(SystemDesc, Default)]
+pub struct BombSystem {
+    /// Whether a bomb button was already held last frame
+    held: bool,
+}
+
+impl<'s> System<'s> for BombSystem {
+    type SystemData = (
+        Read<'s, Time>,
+        Read<'s, LazyUpdate>,
+        Entities<'s>,
+        Read<'s, InputHandler<PsychoBindingTypes>>,
+        Read<'s, GameplayConfig>,
+        Write<'s, Bombs>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, BallEnemy>,
+        ReadStorage<'s, InScreen>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Moving>,
+        ReadStorage<'s, Circle>,
+        ReadStorage<'s, Color>,
+        WriteStorage<'s, Boss>,
+        Write<'s, Score>,
+        Write<'s, Stats>,
+        Write<'s, PsychoMeter>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            time,
+            lazy,
+            entities,
+            input,
+            config,
+            mut bombs,
+            players,
+            enemies,
+            in_screens,
+            transforms,
+            movings,
+            circles,
+            colors,
+            mut bosses,
+            mut score,
+            mut stats,
+            mut meter,
+        ): Self::SystemData,
+    ) {
+        // Bombs are shared, so any living player can use them
+        let held = (&players).join().any(|player| {
+            input
+                .action_is_down(&ActionBinding::Bomb(player.id))
+                .unwrap_or(false)
+        });
+        let pressed = held && !self.held;
+        self.held = held;
+        if !pressed || bombs.remaining == 0 {
+            return;
+        }
+        bombs.remaining -= 1;
+        stats.bombs_used += 1;
+
+        let creator = LazyCreator::new(&lazy, &entities);
+        for (e_id, enemy, _, t, m, c, color) in (
+            &entities,
+            &enemies,
+            &in_screens,
+            &transforms,
+            &movings,
+            &circles,
+            &colors,
+        )
+            .join()
+        {
+            let dead = bosses
+                .get_mut(e_id)
+                .map(|boss| boss.damage(config.bomb_boss_damage))
+                .unwrap_or(true);
+            if dead {
+                entities.delete(e_id).unwrap();
+                enemy.on_destroy(&time, &creator, t, m, c, color);
+                // Clearing obstacles isn't a kill
+                if matches!(enemy, BallEnemy::Obstacle) {
+                    continue;
+                }
+                let points = bosses
+                    .get(e_id)
+                    .map_or_else(|| enemy.points(c.radius), Boss::points);
+                score.add_kill(points, time.absolute_time_seconds(), &config);
+                stats.add_kill(enemy, bosses.contains(e_id));
+                meter.fill(config.kill_meter);
+            } else {
+                create_explosion(&time, &creator, t.0, c.radius, 25, color);
+            }
+        }
+    }
+}
@@ -1,15 +1,25 @@
 use amethyst::{
     core::{math::Vector2, timing::Time},
     derive::SystemDesc,
-    ecs::{Entities, Join, LazyUpdate, Read, ReadStorage, System, SystemData, WriteStorage},
+    ecs::{Entities, Join, LazyUpdate, Read, ReadStorage, System, SystemData, Write, WriteStorage},
     input::InputHandler,
 };
+use rand::Rng;
 
 use crate::{
-    components::{circle::collides, BallEnemy, Circle, Color, InScreen, Player, Transform},
+    components::{
+        circle::collides, Arsenal, BallEnemy, Circle, Color, Dash, Gun, InScreen, Invulnerable,
+        Pickup, Player, Shield, Transform,
+    },
+    config::GameplayConfig,
+    difficulty::Difficulty,
     input::{AxisBinding, PsychoBindingTypes},
-    systems::particles::create_explosion,
-    utils::creator::LazyCreator,
+    systems::{
+        gameplay::{practice::Practice, score::Score, slow_motion::SlowMotion, stats::Stats},
+        particles::create_explosion,
+        player::respawn::Lives,
+    },
+    utils::{creator::LazyCreator, random::GameRng},
 };
 
 #[derive(SystemDesc, Default)]
@@ -26,25 +36,134 @@ impl<'s> System<'s> for CollisionSystem {
         ReadStorage<'s, BallEnemy>,
         ReadStorage<'s, InScreen>,
         ReadStorage<'s, Color>,
+        ReadStorage<'s, Invulnerable>,
+        Read<'s, GameplayConfig>,
+        Write<'s, Lives>,
+        ReadStorage<'s, Pickup>,
+        ReadStorage<'s, Shield>,
+        WriteStorage<'s, Gun>,
+        Read<'s, Arsenal>,
+        Write<'s, SlowMotion>,
+        ReadStorage<'s, Dash>,
+        Write<'s, Score>,
+        Write<'s, GameRng>,
+        Write<'s, Stats>,
+        Read<'s, Difficulty>,
+        Read<'s, Practice>,
     );
 
     fn run(
         &mut self,
-        (time, lazy, entities, players, transforms, circles, enemies, in_screens, colors): Self::SystemData,
+        (
+            time,
+            lazy,
+            entities,
+            players,
+            transforms,
+            circles,
+            enemies,
+            in_screens,
+            colors,
+            invulnerables,
+            config,
+            mut lives,
+            pickups,
+            shields,
+            mut guns,
+            arsenal,
+            mut slow_motion,
+            dashes,
+            mut score,
+            mut rng,
+            mut stats,
+            difficulty,
+            practice,
+        ): Self::SystemData,
     ) {
+        let creator = LazyCreator::new(&lazy, &entities);
+        let now = time.absolute_time_seconds();
+
+        // Pickups can be taken even while invulnerable
+        for (k_id, pickup, k_c, k_t) in (&entities, &pickups, &circles, &transforms).join() {
+            let taker = (&entities, &players, &circles, &transforms)
+                .join()
+                .find(|(_, _, p_c, p_t)| collides(p_t, p_c, k_t, k_c, 0.))
+                .map(|(p_id, ..)| p_id);
+            let p_id = match taker {
+                Some(p_id) => p_id,
+                None => continue,
+            };
+            entities.delete(k_id).unwrap();
+            create_explosion(&time, &creator, k_t.0, k_c.radius, 15, &pickup.color());
+            match pickup {
+                Pickup::ExtraLife => lives.remaining = lives.remaining.saturating_add(1),
+                Pickup::Weapon => {
+                    let n = arsenal.weapons.len();
+                    if let (Some(gun), true) = (guns.get_mut(p_id), n > 1) {
+                        gun.switch(rng.rng.gen_range(1, n) as isize, &arsenal);
+                    }
+                }
+                Pickup::Shield => {
+                    lazy.insert(p_id, Shield);
+                    lazy.insert(p_id, pickup.color());
+                }
+                Pickup::SlowMotion => {
+                    slow_motion.until =
+                        Some(time.absolute_real_time_seconds() + config.slow_motion_duration);
+                }
+            }
+        }
+        if practice.invincible {
+            return;
+        }
+
         let enemies = (&enemies, &in_screens, &circles, &transforms)
             .join()
             .map(|(.., circle, transform)| (circle, transform))
             .collect::<Vec<_>>();
-        let creator = LazyCreator::new(&lazy, &entities);
-        for (_player, p_id, p_c, p_t, color) in
-            (&players, &entities, &circles, &transforms, &colors).join()
+        let mut alive = (&players).join().count();
+        for (player, p_id, p_c, p_t, color, _) in (
+            &players,
+            &entities,
+            &circles,
+            &transforms,
+            &colors,
+            !&invulnerables,
+        )
+            .join()
         {
+            // Dashing players can't be hit
+            if dashes.get(p_id).map_or(false, |dash| dash.is_active(now)) {
+                continue;
+            }
             for (e_c, e_t) in enemies.iter() {
-                if collides(p_t, p_c, e_t, e_c, 2.) {
-                    // Do something prettier eventually
-                    entities.delete(p_id).unwrap();
-                    create_explosion(&time, &creator, p_t.0, p_c.radius, 50, color);
+                if collides(p_t, p_c, e_t, e_c, difficulty.hitbox_tolerance()) {
+                    score.break_combo();
+                    if shields.contains(p_id) {
+                        // The shield breaks instead of the player
+                        lazy.remove::<Shield>(p_id);
+                        lazy.insert(p_id, Player::color(player.id));
+                        lazy.insert(
+                            p_id,
+                            Invulnerable {
+                                until: now + config.shield_invulnerability,
+                            },
+                        );
+                        create_explosion(&time, &creator, p_t.0, p_c.radius, 25, color);
+                    } else {
+                        // Do something prettier eventually
+                        entities.delete(p_id).unwrap();
+                        create_explosion(&time, &creator, p_t.0, p_c.radius, 50, color);
+                        alive -= 1;
+                        stats.deaths += 1;
+                        // Partners can revive the player, a life is only lost
+                        // when everyone is dead
+                        if alive > 0 {
+                            creator.create_downed(player.id, p_t.0);
+                        } else {
+                            lives.lose_life(now, &config);
+                        }
+                    }
                     break;
                 }
             }
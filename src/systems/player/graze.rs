@@ -0,0 +1,134 @@
+use amethyst::{
+    derive::SystemDesc,
+    ecs::{Entities, Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+};
+
+use crate::{
+    components::{circle::collides, BallEnemy, Circle, Graze, InScreen, Player, Transform},
+    config::GameplayConfig,
+    difficulty::Difficulty,
+    systems::gameplay::{psycho::PsychoMeter, score::Score, stats::Stats},
+};
+
+/// Next graze state of an enemy, and whether it just grazed a player, which
+/// is when it leaves the band around them without having hit anyone
+fn update(graze: Option<Graze>, in_band: bool, hit: bool) -> (Option<Graze>, bool) {
+    match graze {
+        Some(Graze::Done) => (graze, false),
+        _ if hit => (Some(Graze::Done), false),
+        _ if in_band => (Some(Graze::InBand), false),
+        Some(Graze::InBand) => (Some(Graze::Done), true),
+        None => (None, false),
+    }
+}
+
+/// Rewards enemies passing close to a player without hitting them, once per
+/// enemy. Obstacles don't count, as they could be grazed for as long as they last.
+#[derive(SystemDesc, Default)]
+pub struct GrazeSystem;
+
+impl<'s> System<'s> for GrazeSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, BallEnemy>,
+        ReadStorage<'s, InScreen>,
+        WriteStorage<'s, Graze>,
+        ReadStorage<'s, Circle>,
+        ReadStorage<'s, Transform>,
+        Read<'s, GameplayConfig>,
+        Read<'s, Difficulty>,
+        Write<'s, Score>,
+        Write<'s, PsychoMeter>,
+        Write<'s, Stats>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            players,
+            enemies,
+            in_screens,
+            mut grazes,
+            circles,
+            transforms,
+            config,
+            difficulty,
+            mut score,
+            mut meter,
+            mut stats,
+        ): Self::SystemData,
+    ) {
+        let players = (&players, &circles, &transforms)
+            .join()
+            .map(|(_, circle, transform)| (circle, transform))
+            .collect::<Vec<_>>();
+        for (e_id, enemy, _, e_c, e_t) in
+            (&entities, &enemies, &in_screens, &circles, &transforms).join()
+        {
+            if matches!(enemy, BallEnemy::Obstacle) {
+                continue;
+            }
+            // The band is the hitbox grown by the graze distance
+            let in_band = players
+                .iter()
+                .any(|(p_c, p_t)| collides(p_t, p_c, e_t, e_c, -config.graze_distance));
+            let hit = in_band
+                && players
+                    .iter()
+                    .any(|(p_c, p_t)| collides(p_t, p_c, e_t, e_c, difficulty.hitbox_tolerance()));
+            let old = grazes.get(e_id).copied();
+            let (new, grazed) = update(old, in_band, hit);
+            if new != old {
+                if let Some(graze) = new {
+                    grazes.insert(e_id, graze).unwrap();
+                }
+            }
+            if grazed {
+                score.points += config.graze_points;
+                meter.fill(config.graze_meter);
+                stats.grazes += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graze_when_passing() {
+        let (graze, grazed) = update(None, true, false);
+        assert_eq!(graze, Some(Graze::InBand));
+        assert!(!grazed);
+        // Still close, nothing yet
+        assert_eq!(update(graze, true, false), (Some(Graze::InBand), false));
+        // Left without hitting
+        assert_eq!(update(graze, false, false), (Some(Graze::Done), true));
+        // Only once
+        assert_eq!(
+            update(Some(Graze::Done), true, false),
+            (Some(Graze::Done), false)
+        );
+        assert_eq!(
+            update(Some(Graze::Done), false, false),
+            (Some(Graze::Done), false)
+        );
+    }
+
+    #[test]
+    fn test_no_graze_on_hit() {
+        let (graze, _) = update(None, true, false);
+        let (graze, grazed) = update(graze, true, true);
+        assert_eq!(graze, Some(Graze::Done));
+        assert!(!grazed);
+        assert_eq!(update(graze, false, false), (Some(Graze::Done), false));
+    }
+
+    #[test]
+    fn test_no_graze_when_far() {
+        assert_eq!(update(None, false, false), (None, false));
+    }
+}
@@ -1,7 +1,13 @@
+pub mod bomb;
 mod collision;
+mod graze;
 pub mod movement;
+pub mod respawn;
 mod shoot;
 
+pub use bomb::BombSystem;
 pub use collision::CollisionSystem;
+pub use graze::GrazeSystem;
 pub use movement::MoveSystem;
+pub use respawn::RespawnSystem;
 pub use shoot::ShootSystem;
@@ -1,47 +1,91 @@
 use amethyst::{
-    core::math::{Point2, Vector2},
+    core::{math::Vector2, timing::Time},
     derive::SystemDesc,
-    ecs::{Join, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+    ecs::{Entities, Join, LazyUpdate, Read, ReadStorage, System, SystemData, WriteStorage},
     input::InputHandler,
 };
 
 use crate::{
-    components::{Player, Transform},
-    input::{AxisBinding, PsychoBindingTypes},
+    components::{Circle, Color, Dash, Player, Transform},
+    config::GameplayConfig,
+    input::{axis_value, ActionBinding, AxisBinding, PsychoBindingTypes},
+    systems::{gameplay::play_area::PlayArea, particles::create_trail},
+    utils::creator::LazyCreator,
 };
 
 #[derive(SystemDesc, Default)]
 pub struct MoveSystem;
 
-const PSYCHO_SPEED: f32 = 8.;
-
-#[derive(Debug, Clone)]
-pub struct PlayerPosition(pub Point2<f32>);
-
-impl Default for PlayerPosition {
-    fn default() -> Self {
-        Self(Point2::new(0., 0.))
-    }
-}
+/// In pixels per second
+const PSYCHO_SPEED: f32 = 480.;
 
 impl<'s> System<'s> for MoveSystem {
     type SystemData = (
+        Read<'s, Time>,
         WriteStorage<'s, Transform>,
-        ReadStorage<'s, Player>,
+        WriteStorage<'s, Player>,
         Read<'s, InputHandler<PsychoBindingTypes>>,
-        Write<'s, PlayerPosition>,
+        ReadStorage<'s, Circle>,
+        Read<'s, PlayArea>,
+        WriteStorage<'s, Dash>,
+        ReadStorage<'s, Color>,
+        Read<'s, GameplayConfig>,
+        Read<'s, LazyUpdate>,
+        Entities<'s>,
     );
 
-    fn run(&mut self, (mut transforms, player, input, mut player_pos): Self::SystemData) {
-        for (_player, transform) in (&player, &mut transforms).join() {
+    fn run(
+        &mut self,
+        (
+            time,
+            mut transforms,
+            mut players,
+            input,
+            circles,
+            area,
+            mut dashes,
+            colors,
+            config,
+            lazy,
+            entities,
+        ): Self::SystemData,
+    ) {
+        let now = time.absolute_time_seconds();
+        let creator = LazyCreator::new(&lazy, &entities);
+        for (player, transform, circle, dash, color) in (
+            &mut players,
+            &mut transforms,
+            &circles,
+            &mut dashes,
+            &colors,
+        )
+            .join()
+        {
+            let id = player.id;
             let dir = Vector2::new(
-                input.axis_value(&AxisBinding::Horizontal).unwrap(),
-                -input.axis_value(&AxisBinding::Vertical).unwrap(),
+                axis_value(&input, &AxisBinding::Horizontal(id)),
+                -axis_value(&input, &AxisBinding::Vertical(id)),
             );
-            if dir.x != 0. || dir.y != 0. {
-                transform.0 += PSYCHO_SPEED * dir.normalize();
+            let moving = dir.x != 0. || dir.y != 0.;
+            let dash_pressed = input
+                .action_is_down(&ActionBinding::Dash(id))
+                .unwrap_or(false);
+            // Dashes go in the direction the player was moving when they started
+            if moving && dash_pressed && now >= dash.ready_at {
+                dash.velocity = config.dash_speed * dir.normalize();
+                dash.until = now + config.dash_duration;
+                dash.ready_at = now + config.dash_cooldown;
             }
-            player_pos.0 = transform.0;
+            player.velocity = if dash.is_active(now) {
+                create_trail(&time, &creator, transform.0, circle.radius, color);
+                dash.velocity
+            } else if moving {
+                PSYCHO_SPEED * dir.normalize()
+            } else {
+                Vector2::new(0., 0.)
+            };
+            transform.0 += player.velocity * time.delta_seconds();
+            transform.0 = area.clamp(transform.0, circle.radius);
         }
     }
 }
@@ -0,0 +1,193 @@
+use amethyst::{
+    core::{
+        math::{Point2, Vector2},
+        timing::Time,
+    },
+    derive::SystemDesc,
+    ecs::{Entities, Join, LazyUpdate, Read, ReadStorage, System, SystemData, Write, WriteStorage},
+};
+
+use crate::{
+    components::{BallEnemy, Boss, Circle, Color, Downed, Invulnerable, Player, Ring, Transform},
+    config::GameplayConfig,
+    systems::{gameplay::play_area::PlayArea, particles::create_explosion, player::bomb::Bombs},
+    utils::creator::LazyCreator,
+};
+
+/// How many seconds each blink takes while invulnerable
+const BLINK_PERIOD: f64 = 0.2;
+
+/// How many players are playing
+#[derive(Debug)]
+pub struct PlayerCount(pub usize);
+
+impl Default for PlayerCount {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+/// Distance between players when they respawn
+const SPAWN_SPACING: f32 = 80.;
+
+/// Where player `id` appears around `center`, so players don't overlap
+pub fn spawn_point(center: Point2<f32>, id: usize, count: usize) -> Point2<f32> {
+    let offset = id as f32 - (count - 1) as f32 / 2.;
+    center + Vector2::new(offset * SPAWN_SPACING, 0.)
+}
+
+/// Lives shared by all players, and when they will respawn if they are all dead
+#[derive(Debug, Default)]
+pub struct Lives {
+    pub remaining: u8,
+    pub respawn_at: Option<f64>,
+}
+
+impl Lives {
+    pub fn new(remaining: u8) -> Self {
+        Self {
+            remaining,
+            respawn_at: None,
+        }
+    }
+
+    /// Called when the last living player dies. Schedules a respawn if there
+    /// are lives left.
+    pub fn lose_life(&mut self, now: f64, config: &GameplayConfig) {
+        self.remaining = self.remaining.saturating_sub(1);
+        if self.remaining > 0 {
+            self.respawn_at = Some(now + config.respawn_delay);
+        }
+    }
+}
+
+/// Respawns the players after they all die, clearing enemies near the respawn
+/// point and refilling their bombs, and makes them blink while invulnerable.
+/// Also revives downed players when a partner stays close to them.
+#[derive(SystemDesc, Default)]
+pub struct RespawnSystem;
+
+impl<'s> System<'s> for RespawnSystem {
+    type SystemData = (
+        Read<'s, Time>,
+        Read<'s, LazyUpdate>,
+        Entities<'s>,
+        Read<'s, GameplayConfig>,
+        Read<'s, PlayArea>,
+        Write<'s, Lives>,
+        Write<'s, Bombs>,
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, BallEnemy>,
+        ReadStorage<'s, Boss>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Circle>,
+        WriteStorage<'s, Invulnerable>,
+        WriteStorage<'s, Color>,
+        Read<'s, PlayerCount>,
+        WriteStorage<'s, Downed>,
+        WriteStorage<'s, Ring>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            time,
+            lazy,
+            entities,
+            config,
+            area,
+            mut lives,
+            mut bombs,
+            players,
+            enemies,
+            bosses,
+            transforms,
+            circles,
+            mut invulnerables,
+            mut colors,
+            player_count,
+            mut downeds,
+            mut rings,
+        ): Self::SystemData,
+    ) {
+        let now = time.absolute_time_seconds();
+        let creator = LazyCreator::new(&lazy, &entities);
+        if lives.respawn_at.map_or(false, |at| at <= now) {
+            lives.respawn_at = None;
+            bombs.remaining = config.bombs;
+            let center = area.center();
+            // Everyone comes back, including downed players
+            for (d_id, _) in (&entities, &downeds).join() {
+                entities.delete(d_id).unwrap();
+            }
+            for id in 0..player_count.0 {
+                creator.create_player(
+                    id,
+                    spawn_point(center, id, player_count.0),
+                    Some(Invulnerable {
+                        until: now + config.invulnerability,
+                    }),
+                );
+            }
+            for (e_id, _enemy, _boss, t, c, color) in (
+                &entities,
+                &enemies,
+                !&bosses,
+                &transforms,
+                &circles,
+                &colors,
+            )
+                .join()
+            {
+                if (t.0 - center).norm() <= config.respawn_clear_radius + c.radius {
+                    entities.delete(e_id).unwrap();
+                    create_explosion(&time, &creator, t.0, c.radius, 25, color);
+                }
+            }
+        }
+
+        let living = (&players, &transforms, &circles)
+            .join()
+            .map(|(_, t, c)| (t.0, c.radius))
+            .collect::<Vec<_>>();
+        for (d_id, downed, t, c, ring) in
+            (&entities, &mut downeds, &transforms, &circles, &mut rings).join()
+        {
+            let reviving = living
+                .iter()
+                .any(|(pos, r)| (pos - t.0).norm() <= r + c.radius + config.revive_radius);
+            if reviving {
+                downed.progress += time.delta_seconds() as f64;
+            }
+            // The ring fills up as the player is revived
+            let progress = (downed.progress / config.revive_time).min(1.) as f32;
+            ring.0 = 0.9 * (1. - progress);
+            if downed.progress >= config.revive_time {
+                entities.delete(d_id).unwrap();
+                creator.create_player(
+                    downed.id,
+                    t.0,
+                    Some(Invulnerable {
+                        until: now + config.invulnerability,
+                    }),
+                );
+            }
+        }
+
+        let mut expired = Vec::new();
+        for (p_id, _player, invulnerable, color) in
+            (&entities, &players, &invulnerables, &mut colors).join()
+        {
+            if invulnerable.until <= now {
+                color.0[3] = 1.;
+                expired.push(p_id);
+            } else {
+                let blink_on = ((invulnerable.until - now) / BLINK_PERIOD) as u64 % 2 == 0;
+                color.0[3] = if blink_on { 1. } else { 0.3 };
+            }
+        }
+        for p_id in expired {
+            invulnerables.remove(p_id);
+        }
+    }
+}
@@ -1,30 +1,37 @@
 use amethyst::{
-    core::{math::Point2, timing::Time},
+    core::{
+        math::{Point2, Rotation2, Vector2},
+        timing::Time,
+    },
     derive::SystemDesc,
-    ecs::{Entities, Join, LazyUpdate, Read, ReadStorage, System, SystemData},
+    ecs::{Entities, Join, LazyUpdate, Read, ReadStorage, System, SystemData, Write, WriteStorage},
     input::InputHandler,
     prelude::*,
 };
 
 use crate::{
-    components::{Circle, Color, InScreen, Moving, Player, Shot, Transform},
-    input::{ActionBinding, PsychoBindingTypes},
+    components::{Arsenal, Circle, Color, Gun, InScreen, Moving, Player, Shot, Transform, Weapon},
+    input::{axis_value, ActionBinding, AxisBinding, PsychoBindingTypes},
+    systems::gameplay::stats::Stats,
 };
+use std::collections::HashMap;
 
-#[derive(SystemDesc)]
-pub struct ShootSystem {
-    last_shot: f64,
-}
+/// How far the aim stick must be pushed to aim and fire
+const AIM_THRESHOLD: f32 = 0.4;
 
-impl Default for ShootSystem {
-    fn default() -> Self {
-        Self { last_shot: -100. }
-    }
+/// Input state kept between frames for each player
+#[derive(Default)]
+struct Shooter {
+    /// Whether a switch button was already held last frame
+    switching: bool,
+    /// Last direction given by the aim axes, used when they are released
+    last_aim: Option<Vector2<f32>>,
 }
 
-const SHOT_INTERVAL: f64 = 0.18;
-const SHOT_SPEED: f32 = 20.;
-const SHOT_RADIUS: f32 = 5.;
+#[derive(SystemDesc, Default)]
+pub struct ShootSystem {
+    shooters: HashMap<usize, Shooter>,
+}
 
 impl<'s> System<'s> for ShootSystem {
     type SystemData = (
@@ -32,44 +39,133 @@ impl<'s> System<'s> for ShootSystem {
         ReadStorage<'s, Transform>,
         ReadStorage<'s, Player>,
         ReadStorage<'s, Circle>,
+        WriteStorage<'s, Gun>,
+        Read<'s, Arsenal>,
         Read<'s, InputHandler<PsychoBindingTypes>>,
         Entities<'s>,
         Read<'s, LazyUpdate>,
+        Write<'s, Stats>,
     );
 
     fn run(
         &mut self,
-        (time, transforms, player, circles, input, entities, lazy): Self::SystemData,
+        (time, transforms, player, circles, mut guns, arsenal, input, entities, lazy, mut stats): Self::SystemData,
     ) {
-        if time.absolute_time_seconds() - self.last_shot < SHOT_INTERVAL {
-            return ();
-        }
-        for (_player, circle, transform) in (&player, &circles, &transforms).join() {
-            if input.action_is_down(&ActionBinding::Shoot).unwrap() {
-                self.last_shot = time.absolute_time_seconds();
-                let r = circle.radius;
-                let center = transform.0;
-                let mouse = {
-                    if let Some((mx, my)) = input.mouse_position() {
-                        Point2::new(mx, my)
-                    } else {
-                        return;
-                    }
-                };
+        let now = time.absolute_time_seconds();
+        let held = |action| input.action_is_down(&action).unwrap_or(false);
+        let mouse = input.mouse_position().map(|(mx, my)| Point2::new(mx, my));
+
+        for (player, circle, transform, gun) in (&player, &circles, &transforms, &mut guns).join() {
+            let id = player.id;
+            let shooter = self.shooters.entry(id).or_default();
+            let switch = match (
+                held(ActionBinding::NextWeapon(id)),
+                held(ActionBinding::PreviousWeapon(id)),
+            ) {
+                (true, false) => 1,
+                (false, true) => -1,
+                _ => 0,
+            };
+            if switch != 0 && !shooter.switching {
+                gun.switch(switch, &arsenal);
+            }
+            shooter.switching = switch != 0;
+            // Aim axes (like a gamepad stick) take precedence over the mouse, and
+            // fire automatically while pushed
+            let aim = Vector2::new(
+                axis_value(&input, &AxisBinding::AimHorizontal(id)),
+                -axis_value(&input, &AxisBinding::AimVertical(id)),
+            );
+            let stick = if aim.norm() >= AIM_THRESHOLD {
+                shooter.last_aim = Some(aim.normalize());
+                true
+            } else {
+                false
+            };
+            let shooting = held(ActionBinding::Shoot(id)) || stick;
+            // There's a single mouse, so it's for the first player
+            let mouse = mouse.filter(|_| id == 0);
 
-                let dir = (mouse - center).normalize();
-                let shot_center = center + dir * (r - SHOT_RADIUS);
+            let weapon = &arsenal.weapons[gun.weapon];
+            let dir = match (stick, mouse, shooter.last_aim) {
+                (false, Some(mouse), _) => (mouse - transform.0).normalize(),
+                (_, _, Some(aim)) => aim,
+                // Nowhere to aim at
+                _ => continue,
+            };
+            // Returns how many shots were fired
+            let fire = |scale: f32| {
+                let mut count = 0;
+                for (dir, shot) in weapon_shots(weapon, dir, scale) {
+                    count += 1;
+                    let radius = weapon.radius * scale;
+                    lazy.create_entity(&entities)
+                        .with(Transform::from(
+                            transform.0 + dir * (circle.radius - radius),
+                        ))
+                        .with(Circle::with_radius(radius))
+                        .with(Color::rgb(0.1, 0.1, 0.9))
+                        .with(Moving::from(dir * weapon.speed))
+                        .with(shot)
+                        // It is fine to delete a shot as soon as it spawns
+                        .with(InScreen)
+                        .build();
+                }
+                count
+            };
 
-                lazy.create_entity(&entities)
-                    .with(Transform::from(shot_center))
-                    .with(Circle::with_radius(SHOT_RADIUS))
-                    .with(Color::rgb(0.1, 0.1, 0.9))
-                    .with(Moving::from(dir * SHOT_SPEED))
-                    .with(Shot)
-                    // It is fine to delete a shot as soon as it spawns
-                    .with(InScreen)
-                    .build();
+            if let Some(charge) = &weapon.charge {
+                // Charged weapons fire when the button is released
+                match gun.charging_since {
+                    None if shooting && gun.ready(now, weapon) => gun.charging_since = Some(now),
+                    Some(since) if !shooting => {
+                        let charged = ((now - since) / charge.time).min(1.) as f32;
+                        stats.shots_fired += fire(1. + charged * (charge.max_scale - 1.));
+                        gun.charging_since = None;
+                        gun.last_shot = Some(now);
+                    }
+                    _ => {}
+                }
+            } else if gun.burst_left > 0 {
+                // Keep firing the rest of the burst even if the button was released
+                let delay = weapon.burst.as_ref().map_or(0., |burst| burst.delay);
+                if gun.last_shot.map_or(true, |last| now - last >= delay) {
+                    stats.shots_fired += fire(1.);
+                    gun.burst_left -= 1;
+                    gun.last_shot = Some(now);
+                }
+            } else if shooting && gun.ready(now, weapon) {
+                stats.shots_fired += fire(1.);
+                gun.burst_left = weapon
+                    .burst
+                    .as_ref()
+                    .map_or(0, |burst| burst.shots.saturating_sub(1));
+                gun.last_shot = Some(now);
             }
         }
     }
 }
+
+/// Directions and shots fired by a weapon aiming at `dir`. `scale` multiplies
+/// the damage, for charged weapons.
+fn weapon_shots(
+    weapon: &Weapon,
+    dir: Vector2<f32>,
+    scale: f32,
+) -> impl Iterator<Item = (Vector2<f32>, Shot)> + '_ {
+    let count = weapon.count.max(1);
+    let (start, step) = if count > 1 {
+        (-weapon.spread / 2., weapon.spread / (count - 1) as f32)
+    } else {
+        (0., 0.)
+    };
+    (0..count).map(move |i| {
+        let angle = start + step * i as f32;
+        let shot = Shot {
+            damage: (weapon.damage as f32 * scale).round() as u32,
+            piercing: weapon.piercing,
+            hit: Vec::new(),
+        };
+        (Rotation2::new(angle.to_radians()) * dir, shot)
+    })
+}
@@ -3,7 +3,15 @@ use amethyst::ecs::{
     LazyUpdate,
 };
 
-use crate::components::{BallEnemy, Circle, Color, Moving, Transform};
+use amethyst::core::math::{Point2, RealField, Vector2};
+
+use crate::{
+    components::{
+        BallEnemy, Boss, Circle, Color, Dash, Downed, Gun, Invulnerable, Moving, Orbit, Pickup,
+        Player, Ring, Transform,
+    },
+    editor::reader::{SatelliteFate, Satellites},
+};
 
 pub struct LazyCreator<'s> {
     pub lazy: &'s LazyUpdate,
@@ -15,6 +23,14 @@ impl BallEnemy {
         match self {
             BallEnemy::Simple => Color::rgb(0.1, 0.1, 0.9),
             BallEnemy::Double => Color::rgb(0.95, 0.3, 0.1),
+            BallEnemy::Obstacle => Color::rgb(0.6, 0.6, 0.6),
+        }
+    }
+
+    pub fn ring(&self) -> Option<Ring> {
+        match self {
+            BallEnemy::Obstacle => Some(Ring(0.6)),
+            _ => None,
         }
     }
 }
@@ -28,6 +44,51 @@ impl<'s> LazyCreator<'s> {
         self.lazy.create_entity(self.entities)
     }
 
+    pub fn create_pickup(&self, pickup: Pickup, pos: Point2<f32>, speed: Vector2<f32>) -> Entity {
+        self.create_entity()
+            .with(pickup)
+            .with(pickup.color())
+            .with(Circle::with_radius(14.))
+            .with(Ring(0.5))
+            .with(Transform::from(pos))
+            .with(Moving::from(speed))
+            .build()
+    }
+
+    pub fn create_player(
+        &self,
+        id: usize,
+        pos: Point2<f32>,
+        invulnerable: Option<Invulnerable>,
+    ) -> Entity {
+        let builder = self
+            .create_entity()
+            .with(Circle::with_radius(24.))
+            .with(Player::color(id))
+            .with(Transform::from(pos))
+            .with(Gun::default())
+            .with(Dash::default())
+            .with(Player::new(id));
+        if let Some(invulnerable) = invulnerable {
+            builder.with(invulnerable).build()
+        } else {
+            builder.build()
+        }
+    }
+
+    /// Creates the marker left by a dead player that can be revived
+    pub fn create_downed(&self, id: usize, pos: Point2<f32>) -> Entity {
+        let mut color = Player::color(id);
+        color.0[3] = 0.5;
+        self.create_entity()
+            .with(Downed { id, progress: 0. })
+            .with(Circle::with_radius(24.))
+            .with(Ring(0.9))
+            .with(color)
+            .with(Transform::from(pos))
+            .build()
+    }
+
     pub fn create_enemy(
         &self,
         enemy: BallEnemy,
@@ -35,12 +96,60 @@ impl<'s> LazyCreator<'s> {
         transform: Transform,
         moving: Moving,
     ) -> Entity {
-        self.create_entity()
+        let builder = self
+            .create_entity()
             .with(enemy)
             .with(enemy.default_color())
             .with(circle)
             .with(transform)
-            .with(moving)
+            .with(moving);
+        if let Some(ring) = enemy.ring() {
+            builder.with(ring).build()
+        } else {
+            builder.build()
+        }
+    }
+
+    pub fn create_boss(
+        &self,
+        boss: Boss,
+        circle: Circle,
+        pos: Point2<f32>,
+        speed: Vector2<f32>,
+    ) -> Entity {
+        self.create_entity()
+            .with(BallEnemy::Simple)
+            .with(boss)
+            .with(Color::rgb(0.6, 0.1, 0.7))
+            .with(circle)
+            .with(Transform::from(pos))
+            .with(Moving::from(speed))
             .build()
     }
+
+    /// Creates `satellites` orbiting around `parent`, which is at `center` moving with `speed`
+    pub fn create_satellites(
+        &self,
+        parent: Entity,
+        center: Point2<f32>,
+        speed: Vector2<f32>,
+        satellites: &Satellites,
+    ) {
+        for i in 0..satellites.amount {
+            let orbit = Orbit {
+                parent,
+                radius: satellites.orbit_radius,
+                angular_speed: satellites.angular_speed,
+                angle: f32::two_pi() / (satellites.amount as f32) * (i as f32),
+                fate: satellites.on_parent_death.unwrap_or(SatelliteFate::Release),
+            };
+            let satellite = self.create_enemy(
+                satellites.enemy,
+                Circle::with_radius(satellites.radius),
+                Transform::from(center + orbit.offset()),
+                Moving::from(speed + orbit.tangential_speed()),
+            );
+            self.lazy.insert(satellite, orbit);
+        }
+    }
 }
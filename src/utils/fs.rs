@@ -1,6 +1,9 @@
 use std::env;
 use std::path::PathBuf;
 
+/// Name of the directory for user files inside the platform's data directory
+const USER_DIR_NAME: &str = "psycho_the_ball";
+
 /// Use this instead of application_root_dir, because of Windows problems.
 /// See https://github.com/rust-lang/rust/issues/42869
 pub fn root() -> PathBuf {
@@ -15,3 +18,17 @@ pub fn root() -> PathBuf {
         .and_then(dunce::canonicalize)
         .expect("Failed to get root dir")
 }
+
+/// Directory for files written by the game, like rebound controls. Falls back
+/// to a `user` directory inside the root on platforms without a data directory,
+/// so the shipped config files are never overwritten.
+pub fn user_dir() -> PathBuf {
+    dirs::data_dir()
+        .map(|dir| dir.join(USER_DIR_NAME))
+        .unwrap_or_else(|| root().join("user"))
+}
+
+/// Where the user's copy of a config file is saved
+pub fn user_config_file(name: &str) -> PathBuf {
+    user_dir().join("config").join(name)
+}
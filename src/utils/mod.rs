@@ -1,2 +1,3 @@
 pub mod creator;
 pub mod fs;
+pub mod random;
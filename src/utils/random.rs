@@ -0,0 +1,25 @@
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Random number generator for everything that affects gameplay, so a run
+/// can be told apart by its seed. Purely visual randomness like particles
+/// doesn't need it.
+#[derive(Debug)]
+pub struct GameRng {
+    pub seed: u64,
+    pub rng: StdRng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(rand::random())
+    }
+}